@@ -1,3 +1,14 @@
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "mint")]
+extern crate mint;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "cgmath")]
+extern crate cgmath;
+
 mod math;
 
 use math::*;
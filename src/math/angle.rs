@@ -0,0 +1,185 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::fmt::{Display, Formatter, Error};
+use super::scalar::{self, TAU};
+
+// angle newtypes. the rotation and projection constructors on Matrix accept
+// impl Into<Radians>, so Degrees convert correctly and bare f32 is radians.
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct Radians(pub f32);
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct Degrees(pub f32);
+
+impl From<f32> for Radians {
+    fn from(value: f32) -> Radians {
+        Radians(value)
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(value: Degrees) -> Radians {
+        Radians(value.0.to_radians())
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(value: Radians) -> Degrees {
+        Degrees(value.0.to_degrees())
+    }
+}
+
+macro_rules! angle {
+    ($t:ident, $full:expr) => {
+        impl Add<$t> for $t {
+            type Output = $t;
+            fn add(self, rhs: $t) -> $t { $t(self.0 + rhs.0) }
+        }
+
+        impl Sub<$t> for $t {
+            type Output = $t;
+            fn sub(self, rhs: $t) -> $t { $t(self.0 - rhs.0) }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = $t;
+            fn mul(self, rhs: f32) -> $t { $t(self.0 * rhs) }
+        }
+
+        impl Div<f32> for $t {
+            type Output = $t;
+            fn div(self, rhs: f32) -> $t { $t(self.0 / rhs) }
+        }
+
+        impl Div<$t> for $t {
+            type Output = f32;
+            fn div(self, rhs: $t) -> f32 { self.0 / rhs.0 }
+        }
+
+        impl Neg for $t {
+            type Output = $t;
+            fn neg(self) -> $t { $t(-self.0) }
+        }
+
+        impl_assign_op!($t, $t,  AddAssign, add_assign, +);
+        impl_assign_op!($t, $t,  SubAssign, sub_assign, -);
+        impl_assign_op!($t, f32, MulAssign, mul_assign, *);
+        impl_assign_op!($t, f32, DivAssign, div_assign, /);
+        impl_scalar_mul!($t);
+
+        impl $t {
+
+            pub const FULL: $t = $t($full);
+            pub const HALF: $t = $t($full * 0.5f32);
+
+            pub fn value(&self) -> f32 {
+                self.0
+            }
+
+            // wraps into [-half, half), e.g. [-pi, pi).
+            pub fn wrap_signed(&self) -> $t {
+                $t(scalar::wrap(self.0 + ($full * 0.5f32), $full) - ($full * 0.5f32))
+            }
+
+            // wraps into [0, full), e.g. [0, 2pi).
+            pub fn wrap_positive(&self) -> $t {
+                $t(scalar::wrap(self.0, $full))
+            }
+
+            pub fn shortest_difference(from: $t, to: $t) -> $t {
+                (to - from).wrap_signed()
+            }
+
+            // interpolates along the shortest arc.
+            pub fn lerp(from: $t, to: $t, amount: f32) -> $t {
+                from + ($t::shortest_difference(from, to) * amount)
+            }
+
+            pub fn sin(&self) -> f32 { Radians::from(*self).0.sin() }
+            pub fn cos(&self) -> f32 { Radians::from(*self).0.cos() }
+            pub fn tan(&self) -> f32 { Radians::from(*self).0.tan() }
+        }
+    }
+}
+
+angle!(Radians, TAU);
+angle!(Degrees, 360f32);
+
+impl Display for Radians {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}rad", self.0)
+    }
+}
+
+impl Display for Degrees {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}deg", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Radians, Degrees};
+    use math::scalar::{PI, TAU};
+
+    #[test]
+    fn converts_between_radians_and_degrees() {
+        assert!((Radians::from(Degrees(180f32)).0 - PI).abs() < 1e-6f32);
+        assert!((Degrees::from(Radians(PI * 0.5f32)).0 - 90f32).abs() < 1e-4f32);
+        assert!(Radians::from(2f32) == Radians(2f32));
+        assert!(Degrees(30f32) + Degrees(60f32) == Degrees(90f32));
+        assert!(-Degrees(30f32) * 2f32 == Degrees(-60f32));
+        assert_eq!(Degrees(90f32) / Degrees(30f32), 3f32);
+        assert!((Degrees(30f32).sin() - 0.5f32).abs() < 1e-6f32);
+    }
+
+    #[test]
+    fn wraps_into_range() {
+        assert!(Degrees(540f32).wrap_signed() == Degrees(-180f32));
+        assert!(Degrees(-190f32).wrap_signed() == Degrees(170f32));
+        assert!(Degrees(-90f32).wrap_positive() == Degrees(270f32));
+        assert!(Degrees(720f32).wrap_positive() == Degrees(0f32));
+        let below = Radians(f32::from_bits((-PI).to_bits() + 1));
+        assert!((-PI..PI).contains(&below.wrap_signed().0));
+        assert!((0f32..TAU).contains(&Radians(-1e-8f32).wrap_positive().0));
+        assert!(Degrees(-1e-6f32).wrap_positive() == Degrees(0f32));
+    }
+
+    #[test]
+    fn interpolates_along_the_shortest_arc() {
+        assert!(Degrees::shortest_difference(Degrees(350f32), Degrees(10f32)) == Degrees(20f32));
+        assert!(Degrees::shortest_difference(Degrees(10f32), Degrees(350f32)) == Degrees(-20f32));
+        let halfway = Degrees::lerp(Degrees(350f32), Degrees(10f32), 0.5f32).wrap_positive();
+        assert!(halfway.0.abs() < 1e-4f32 || (halfway.0 - 360f32).abs() < 1e-4f32);
+    }
+}
@@ -0,0 +1,104 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::ops::{Not, BitAnd, BitOr};
+use std::fmt::{Display, Formatter, Error};
+
+// boolean masks returned by the component-wise comparisons (cmplt, cmpeq, ...)
+// of the float and integer vectors, and consumed by their select functions.
+
+macro_rules! bool_vector {
+    ($t:ident, $($c:ident),+) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $t {
+            $(pub $c: bool),+
+        }
+
+        impl $t {
+
+            pub const fn new($($c: bool),+) -> $t {
+                $t { $($c),+ }
+            }
+
+            pub const fn splat(value: bool) -> $t {
+                $t { $($c: value),+ }
+            }
+
+            pub fn any(&self) -> bool {
+                false $(|| self.$c)+
+            }
+
+            pub fn all(&self) -> bool {
+                true $(&& self.$c)+
+            }
+        }
+
+        impl Not for $t {
+            type Output = $t;
+            fn not(self) -> $t {
+                $t { $($c: !self.$c),+ }
+            }
+        }
+
+        impl BitAnd for $t {
+            type Output = $t;
+            fn bitand(self, rhs: $t) -> $t {
+                $t { $($c: self.$c && rhs.$c),+ }
+            }
+        }
+
+        impl BitOr for $t {
+            type Output = $t;
+            fn bitor(self, rhs: $t) -> $t {
+                $t { $($c: self.$c || rhs.$c),+ }
+            }
+        }
+    }
+}
+
+bool_vector!(BVector2, x, y);
+bool_vector!(BVector3, x, y, z);
+bool_vector!(BVector4, x, y, z, w);
+
+impl Display for BVector2 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for BVector3 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl Display for BVector4 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
@@ -26,4 +26,107 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-pub struct BoundingBox;
\ No newline at end of file
+use super::vector3::Vector3;
+use super::bounding_sphere::BoundingSphere;
+use super::query;
+use super::scalar::EPSILON;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct BoundingBox {
+    pub min: Vector3,
+    pub max: Vector3
+}
+
+impl BoundingBox {
+
+    pub const fn new(min: Vector3, max: Vector3) -> BoundingBox {
+        BoundingBox {
+            min,
+            max
+        }
+    }
+
+    pub fn center(&self) -> Vector3 {
+        (self.min + self.max) * 0.5f32
+    }
+
+    pub fn extents(&self) -> Vector3 {
+        (self.max - self.min) * 0.5f32
+    }
+
+    // an empty slice yields an inverted box, which merge treats as empty.
+    pub fn from_points(points: &[Vector3]) -> BoundingBox {
+        let mut min = Vector3::new(f32::MAX, f32::MAX, f32::MAX);
+        let mut max = Vector3::new(-f32::MAX, -f32::MAX, -f32::MAX);
+        for point in points {
+            min = Vector3::min(min, *point);
+            max = Vector3::max(max, *point);
+        }
+        BoundingBox::new(min, max)
+    }
+
+    pub fn merge(value1: BoundingBox, value2: BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: Vector3::min(value1.min, value2.min),
+            max: Vector3::max(value1.max, value2.max)
+        }
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let size = self.max - self.min;
+        2f32 * (((size.x * size.y) + (size.y * size.z)) + (size.z * size.x))
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        query::closest_point_on_box(point, *self)
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x &&
+        point.y >= self.min.y && point.y <= self.max.y &&
+        point.z >= self.min.z && point.z <= self.max.z
+    }
+
+    pub fn contains_box(&self, other: BoundingBox) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    pub fn intersects_box(&self, other: BoundingBox) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x &&
+        self.min.y <= other.max.y && self.max.y >= other.min.y &&
+        self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+
+    pub fn intersects_sphere(&self, sphere: BoundingSphere) -> bool {
+        query::distance_squared_point_box(sphere.center, *self) <= sphere.radius * sphere.radius
+    }
+
+    // distance along the ray in units of direction, 0 when the ray starts inside.
+    pub fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        let mut min = 0f32;
+        let mut max = f32::MAX;
+        for axis in 0..3 {
+            let origin = position[axis];
+            let delta  = direction[axis];
+            if delta.abs() < EPSILON {
+                if origin < self.min[axis] || origin > self.max[axis] {
+                    return None;
+                }
+            } else {
+                let inverse = 1f32 / delta;
+                let mut t1 = (self.min[axis] - origin) * inverse;
+                let mut t2 = (self.max[axis] - origin) * inverse;
+                if t1 > t2 {
+                    std::mem::swap(&mut t1, &mut t2);
+                }
+                min = min.max(t1);
+                max = max.min(t2);
+                if min > max {
+                    return None;
+                }
+            }
+        }
+        Some(min)
+    }
+}
@@ -26,4 +26,91 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-pub struct BoundingFrustum;
\ No newline at end of file
+use super::vector3::Vector3;
+use super::vector4::Vector4;
+use super::matrix::Matrix;
+use super::plane::Plane;
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+
+// planes are extracted from a view projection matrix as in xna, normalized
+// and facing outward, in the order near, far, left, right, top, bottom.
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct BoundingFrustum {
+    pub matrix: Matrix,
+    pub planes: [Plane; 6]
+}
+
+impl BoundingFrustum {
+
+    pub fn new(matrix: Matrix) -> BoundingFrustum {
+        let m = matrix;
+        let planes = [
+            Plane::new(-m.m13, -m.m23, -m.m33, -m.m43),
+            Plane::new(m.m13 - m.m14, m.m23 - m.m24, m.m33 - m.m34, m.m43 - m.m44),
+            Plane::new(-m.m14 - m.m11, -m.m24 - m.m21, -m.m34 - m.m31, -m.m44 - m.m41),
+            Plane::new(m.m11 - m.m14, m.m21 - m.m24, m.m31 - m.m34, m.m41 - m.m44),
+            Plane::new(m.m12 - m.m14, m.m22 - m.m24, m.m32 - m.m34, m.m42 - m.m44),
+            Plane::new(-m.m14 - m.m12, -m.m24 - m.m22, -m.m34 - m.m32, -m.m44 - m.m42)
+        ];
+        BoundingFrustum {
+            matrix,
+            planes: [
+                Plane::normalize(planes[0]),
+                Plane::normalize(planes[1]),
+                Plane::normalize(planes[2]),
+                Plane::normalize(planes[3]),
+                Plane::normalize(planes[4]),
+                Plane::normalize(planes[5])
+            ]
+        }
+    }
+
+    pub fn near   (&self) -> Plane { self.planes[0] }
+    pub fn far    (&self) -> Plane { self.planes[1] }
+    pub fn left   (&self) -> Plane { self.planes[2] }
+    pub fn right  (&self) -> Plane { self.planes[3] }
+    pub fn top    (&self) -> Plane { self.planes[4] }
+    pub fn bottom (&self) -> Plane { self.planes[5] }
+
+    // near corners first, then far, each ordered top left, top right,
+    // bottom right, bottom left.
+    pub fn corners(&self) -> [Vector3; 8] {
+        let inverse = Matrix::invert(self.matrix);
+        let unproject = |x: f32, y: f32, z: f32| {
+            let v = Vector4::new(x, y, z, 1f32) * inverse;
+            Vector3::new(v.x, v.y, v.z) / v.w
+        };
+        [
+            unproject(-1f32,  1f32, 0f32),
+            unproject( 1f32,  1f32, 0f32),
+            unproject( 1f32, -1f32, 0f32),
+            unproject(-1f32, -1f32, 0f32),
+            unproject(-1f32,  1f32, 1f32),
+            unproject( 1f32,  1f32, 1f32),
+            unproject( 1f32, -1f32, 1f32),
+            unproject(-1f32, -1f32, 1f32)
+        ]
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes.iter().all(|plane| Plane::dot_coordinate(*plane, point) <= 0f32)
+    }
+
+    // conservative: boxes near a frustum edge may report true while outside.
+    pub fn intersects_box(&self, bounds: BoundingBox) -> bool {
+        self.planes.iter().all(|plane| {
+            let vertex = Vector3::new(
+                if plane.a >= 0f32 { bounds.min.x } else { bounds.max.x },
+                if plane.b >= 0f32 { bounds.min.y } else { bounds.max.y },
+                if plane.c >= 0f32 { bounds.min.z } else { bounds.max.z });
+            Plane::dot_coordinate(*plane, vertex) <= 0f32
+        })
+    }
+
+    pub fn intersects_sphere(&self, sphere: BoundingSphere) -> bool {
+        self.planes.iter().all(|plane| Plane::dot_coordinate(*plane, sphere.center) <= sphere.radius)
+    }
+}
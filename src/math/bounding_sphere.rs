@@ -26,4 +26,42 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-pub struct BoundingSphere;
\ No newline at end of file
+use super::vector3::Vector3;
+use super::scalar::EPSILON;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct BoundingSphere {
+    pub center: Vector3,
+    pub radius: f32
+}
+
+impl BoundingSphere {
+
+    pub fn new(center: Vector3, radius: f32) -> BoundingSphere {
+        BoundingSphere {
+            center,
+            radius
+        }
+    }
+
+    // distance along the ray in units of direction, 0 when the ray starts inside.
+    pub fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        let m = position - self.center;
+        let a = Vector3::dot(direction, direction);
+        let b = Vector3::dot(m, direction);
+        let c = Vector3::dot(m, m) - (self.radius * self.radius);
+        if c <= 0f32 {
+            return Some(0f32);
+        }
+        // a still ray from outside never gets there.
+        if a <= EPSILON {
+            return None;
+        }
+        let discriminant = (b * b) - (a * c);
+        if b > 0f32 || discriminant < 0f32 {
+            return None;
+        }
+        Some((-b - discriminant.sqrt()) / a)
+    }
+}
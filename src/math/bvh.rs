@@ -0,0 +1,479 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::bounding_frustum::BoundingFrustum;
+use super::oriented_bounding_box::OrientedBoundingBox;
+use super::triangle::Triangle;
+use super::query;
+
+// bounding volume hierarchy over anything with a bounding box. nodes live in
+// a flat array with children stored after their parent, so a refit is a
+// single reverse pass.
+
+const MAX_LEAF_SIZE: usize = 4;
+const SAH_BINS:      usize = 12;
+
+pub trait Primitive {
+    fn bounds(&self) -> BoundingBox;
+    fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32>;
+    fn closest_point(&self, point: Vector3) -> Vector3;
+}
+
+impl Primitive for BoundingBox {
+    fn bounds(&self) -> BoundingBox { *self }
+    fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        BoundingBox::intersects_ray(self, position, direction)
+    }
+    fn closest_point(&self, point: Vector3) -> Vector3 {
+        BoundingBox::closest_point(self, point)
+    }
+}
+
+impl Primitive for BoundingSphere {
+    fn bounds(&self) -> BoundingBox {
+        let radius = Vector3::new(self.radius, self.radius, self.radius);
+        BoundingBox::new(self.center - radius, self.center + radius)
+    }
+    fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        BoundingSphere::intersects_ray(self, position, direction)
+    }
+    fn closest_point(&self, point: Vector3) -> Vector3 {
+        query::closest_point_on_sphere(point, *self)
+    }
+}
+
+impl Primitive for OrientedBoundingBox {
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::from_points(&self.corners())
+    }
+    fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        OrientedBoundingBox::intersects_ray(self, position, direction)
+    }
+    fn closest_point(&self, point: Vector3) -> Vector3 {
+        OrientedBoundingBox::closest_point(self, point)
+    }
+}
+
+impl Primitive for Triangle {
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::from_points(&[self.a, self.b, self.c])
+    }
+    fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        Triangle::intersects_ray(self, position, direction)
+    }
+    fn closest_point(&self, point: Vector3) -> Vector3 {
+        Triangle::closest_point(self, point)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SplitStrategy {
+    SurfaceAreaHeuristic,
+    Median
+}
+
+// index into the primitive slice the hierarchy was built from. distance is
+// along the ray in units of direction for raycasts, euclidean for nearest.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hit {
+    pub index:    usize,
+    pub distance: f32
+}
+
+#[derive(Copy, Clone)]
+struct Node {
+    bounds: BoundingBox,
+    // first child for interior nodes, first entry in indices for leaves.
+    first:  usize,
+    // zero for interior nodes.
+    count:  usize
+}
+
+pub struct Bvh<T: Primitive> {
+    primitives: Vec<T>,
+    indices:    Vec<usize>,
+    nodes:      Vec<Node>
+}
+
+impl<T: Primitive> Bvh<T> {
+
+    pub fn new(primitives: Vec<T>, strategy: SplitStrategy) -> Bvh<T> {
+        let count = primitives.len();
+        let mut bvh = Bvh {
+            primitives,
+            indices: (0..count).collect(),
+            nodes:   Vec::new()
+        };
+        if count > 0 {
+            let bounds:    Vec<BoundingBox> = bvh.primitives.iter().map(|p| p.bounds()).collect();
+            let centroids: Vec<Vector3>     = bounds.iter().map(|b| b.center()).collect();
+            bvh.nodes.push(Node { bounds: bounds[0], first: 0, count: 0 });
+            bvh.build(0, 0, count, &bounds, &centroids, strategy);
+        }
+        bvh
+    }
+
+    pub fn primitives(&self) -> &[T] {
+        &self.primitives
+    }
+
+    // call refit after moving primitives. the topology is kept, so queries
+    // stay correct but slow down if objects move far from where they started.
+    pub fn primitives_mut(&mut self) -> &mut [T] {
+        &mut self.primitives
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    pub fn refit(&mut self) {
+        for i in (0..self.nodes.len()).rev() {
+            let node = self.nodes[i];
+            self.nodes[i].bounds = if node.count > 0 {
+                self.leaf_bounds(node.first, node.first + node.count)
+            } else {
+                BoundingBox::merge(self.nodes[node.first].bounds, self.nodes[node.first + 1].bounds)
+            };
+        }
+    }
+
+    pub fn raycast(&self, position: Vector3, direction: Vector3, max_distance: f32) -> Option<Hit> {
+        self.traverse_ray(position, direction, max_distance, false)
+    }
+
+    // stops at the first hit found rather than the closest, for occlusion tests.
+    pub fn raycast_any(&self, position: Vector3, direction: Vector3, max_distance: f32) -> Option<Hit> {
+        self.traverse_ray(position, direction, max_distance, true)
+    }
+
+    // primitives whose bounds overlap the box.
+    pub fn query_box(&self, bounds: BoundingBox) -> Vec<usize> {
+        self.query(|node| node.intersects_box(bounds))
+    }
+
+    // primitives whose bounds may be inside the frustum.
+    pub fn query_frustum(&self, frustum: &BoundingFrustum) -> Vec<usize> {
+        self.query(|node| frustum.intersects_box(node))
+    }
+
+    pub fn nearest(&self, point: Vector3) -> Option<Hit> {
+        self.nearest_within(point, f32::MAX)
+    }
+
+    pub fn nearest_within(&self, point: Vector3, max_distance: f32) -> Option<Hit> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut best: Option<Hit> = None;
+        let mut best_squared = if max_distance == f32::MAX { f32::MAX } else { max_distance * max_distance };
+        let mut stack = vec![0usize];
+        while let Some(i) = stack.pop() {
+            let node = self.nodes[i];
+            if query::distance_squared_point_box(point, node.bounds) > best_squared {
+                continue;
+            }
+            if node.count > 0 {
+                for &index in &self.indices[node.first..node.first + node.count] {
+                    let distance_squared = (self.primitives[index].closest_point(point) - point).length_squared();
+                    if distance_squared <= best_squared {
+                        best_squared = distance_squared;
+                        best = Some(Hit { index, distance: distance_squared.sqrt() });
+                    }
+                }
+            } else {
+                let left  = query::distance_squared_point_box(point, self.nodes[node.first].bounds);
+                let right = query::distance_squared_point_box(point, self.nodes[node.first + 1].bounds);
+                // push the farther child first so the nearer is visited first.
+                if left < right {
+                    stack.push(node.first + 1);
+                    stack.push(node.first);
+                } else {
+                    stack.push(node.first);
+                    stack.push(node.first + 1);
+                }
+            }
+        }
+        best
+    }
+
+    fn query<F: Fn(BoundingBox) -> bool>(&self, overlaps: F) -> Vec<usize> {
+        let mut result = Vec::new();
+        if self.nodes.is_empty() {
+            return result;
+        }
+        let mut stack = vec![0usize];
+        while let Some(i) = stack.pop() {
+            let node = self.nodes[i];
+            if !overlaps(node.bounds) {
+                continue;
+            }
+            if node.count > 0 {
+                for &index in &self.indices[node.first..node.first + node.count] {
+                    if overlaps(self.primitives[index].bounds()) {
+                        result.push(index);
+                    }
+                }
+            } else {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+            }
+        }
+        result
+    }
+
+    fn traverse_ray(&self, position: Vector3, direction: Vector3, max_distance: f32, any: bool) -> Option<Hit> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut best: Option<Hit> = None;
+        let mut best_distance = max_distance;
+        let mut stack = vec![0usize];
+        while let Some(i) = stack.pop() {
+            let node = self.nodes[i];
+            match node.bounds.intersects_ray(position, direction) {
+                Some(distance) if distance <= best_distance => {},
+                _ => continue
+            }
+            if node.count > 0 {
+                for &index in &self.indices[node.first..node.first + node.count] {
+                    if let Some(distance) = self.primitives[index].intersects_ray(position, direction) {
+                        if distance <= best_distance {
+                            best_distance = distance;
+                            best = Some(Hit { index, distance });
+                            if any {
+                                return best;
+                            }
+                        }
+                    }
+                }
+            } else {
+                let left  = self.nodes[node.first].bounds.intersects_ray(position, direction);
+                let right = self.nodes[node.first + 1].bounds.intersects_ray(position, direction);
+                let left_first = match (left, right) {
+                    (Some(l), Some(r)) => l <= r,
+                    _                  => left.is_some()
+                };
+                if left_first {
+                    stack.push(node.first + 1);
+                    stack.push(node.first);
+                } else {
+                    stack.push(node.first);
+                    stack.push(node.first + 1);
+                }
+            }
+        }
+        best
+    }
+
+    fn leaf_bounds(&self, start: usize, end: usize) -> BoundingBox {
+        self.indices[start + 1..end].iter().fold(self.primitives[self.indices[start]].bounds(), |bounds, &index| {
+            BoundingBox::merge(bounds, self.primitives[index].bounds())
+        })
+    }
+
+    fn build(&mut self, node: usize, start: usize, end: usize, bounds: &[BoundingBox], centroids: &[Vector3], strategy: SplitStrategy) {
+        let indices = &mut self.indices[start..end];
+        let node_bounds = indices[1..].iter().fold(bounds[indices[0]], |b, &i| BoundingBox::merge(b, bounds[i]));
+        self.nodes[node] = Node { bounds: node_bounds, first: start, count: end - start };
+        if indices.len() <= MAX_LEAF_SIZE {
+            return;
+        }
+        let centroid_bounds = BoundingBox::from_points(&indices.iter().map(|&i| centroids[i]).collect::<Vec<_>>());
+        let size = centroid_bounds.max - centroid_bounds.min;
+        let axis = if size.x >= size.y && size.x >= size.z { 0 } else if size.y >= size.z { 1 } else { 2 };
+        if size[axis] <= 0f32 {
+            return;
+        }
+        let mid = match strategy {
+            SplitStrategy::Median => {
+                let mid = indices.len() / 2;
+                indices.select_nth_unstable_by(mid, |&a, &b| centroids[a][axis].total_cmp(&centroids[b][axis]));
+                mid
+            },
+            SplitStrategy::SurfaceAreaHeuristic => {
+                match split_sah(indices, bounds, centroids, centroid_bounds, node_bounds) {
+                    Some(mid) => mid,
+                    None      => return
+                }
+            }
+        };
+        let left = self.nodes.len();
+        self.nodes.push(self.nodes[node]);
+        self.nodes.push(self.nodes[node]);
+        self.nodes[node] = Node { bounds: node_bounds, first: left, count: 0 };
+        self.build(left, start, start + mid, bounds, centroids, strategy);
+        self.build(left + 1, start + mid, end, bounds, centroids, strategy);
+    }
+}
+
+// binned sah over all three axes. partitions indices and returns the split
+// position, or none when keeping the node as a leaf is cheaper.
+fn split_sah(indices: &mut [usize], bounds: &[BoundingBox], centroids: &[Vector3], centroid_bounds: BoundingBox, node_bounds: BoundingBox) -> Option<usize> {
+    let mut best: Option<(usize, usize, f32)> = None;
+    let mut best_cost = indices.len() as f32 * node_bounds.surface_area();
+    for axis in [0, 1, 2] {
+        let min    = centroid_bounds.min[axis];
+        let extent = centroid_bounds.max[axis] - min;
+        if extent <= 0f32 {
+            continue;
+        }
+        let scale = SAH_BINS as f32 / extent;
+        let bin_of = |i: usize| (((centroids[i][axis] - min) * scale) as usize).min(SAH_BINS - 1);
+        let mut bins: [(Option<BoundingBox>, usize); SAH_BINS] = [(None, 0); SAH_BINS];
+        for &i in indices.iter() {
+            let bin = &mut bins[bin_of(i)];
+            bin.0 = Some(bin.0.map_or(bounds[i], |b| BoundingBox::merge(b, bounds[i])));
+            bin.1 += 1;
+        }
+        // cost of splitting after each bin, sweeping from both ends.
+        let mut right_costs = [0f32; SAH_BINS];
+        let mut accumulated: Option<BoundingBox> = None;
+        let mut count = 0;
+        for split in (1..SAH_BINS).rev() {
+            if let Some(b) = bins[split].0 {
+                accumulated = Some(accumulated.map_or(b, |a| BoundingBox::merge(a, b)));
+            }
+            count += bins[split].1;
+            right_costs[split - 1] = accumulated.map_or(0f32, |a| count as f32 * a.surface_area());
+        }
+        let mut accumulated: Option<BoundingBox> = None;
+        let mut count = 0;
+        for (split, bin) in bins.iter().take(SAH_BINS - 1).enumerate() {
+            if let Some(b) = bin.0 {
+                accumulated = Some(accumulated.map_or(b, |a| BoundingBox::merge(a, b)));
+            }
+            count += bin.1;
+            if count == 0 || count == indices.len() {
+                continue;
+            }
+            let cost = accumulated.map_or(0f32, |a| count as f32 * a.surface_area()) + right_costs[split];
+            if cost < best_cost {
+                best_cost = cost;
+                best = Some((axis, split, cost));
+            }
+        }
+    }
+    let (axis, split, _) = best?;
+    let min   = centroid_bounds.min[axis];
+    let scale = SAH_BINS as f32 / (centroid_bounds.max[axis] - min);
+    let mut mid = 0;
+    for i in 0..indices.len() {
+        let bin = (((centroids[indices[i]][axis] - min) * scale) as usize).min(SAH_BINS - 1);
+        if bin <= split {
+            indices.swap(i, mid);
+            mid += 1;
+        }
+    }
+    Some(mid)
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Matrix, BoundingBox, BoundingSphere, BoundingFrustum, Degrees};
+    use super::{Bvh, SplitStrategy, Primitive};
+
+    fn spheres(count: usize) -> Vec<BoundingSphere> {
+        (0..count).map(|i| {
+            let t = i as f32;
+            let center = Vector3::new((t * 7.31f32) % 40f32 - 20f32, (t * 3.17f32) % 40f32 - 20f32, (t * 1.93f32) % 40f32 - 20f32);
+            BoundingSphere::new(center, 0.25f32 + (i % 5) as f32 * 0.2f32)
+        }).collect()
+    }
+
+    fn closest_hit(primitives: &[BoundingSphere], position: Vector3, direction: Vector3) -> Option<f32> {
+        primitives.iter().filter_map(|p| p.intersects_ray(position, direction)).min_by(|a, b| a.total_cmp(b))
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let primitives = spheres(300);
+        for &strategy in &[SplitStrategy::SurfaceAreaHeuristic, SplitStrategy::Median] {
+            let bvh = Bvh::new(primitives.clone(), strategy);
+            for i in 0..20 {
+                let position  = Vector3::new(-30f32, (i as f32) - 10f32, (i as f32 * 0.7f32) - 7f32);
+                let direction = Vector3::new(1f32, 0.1f32 * (i % 3) as f32, -0.05f32 * (i % 4) as f32);
+                let expected  = closest_hit(&primitives, position, direction);
+                let hit = bvh.raycast(position, direction, f32::MAX);
+                assert_eq!(hit.map(|h| h.distance), expected);
+                assert_eq!(bvh.raycast_any(position, direction, f32::MAX).is_some(), expected.is_some());
+
+                let point   = Vector3::new((i as f32 * 2.3f32) - 20f32, 5f32, -3f32);
+                let nearest = bvh.nearest(point).unwrap();
+                let best    = primitives.iter().map(|p| (p.closest_point(point) - point).length()).fold(f32::MAX, f32::min);
+                assert!((nearest.distance - best).abs() < 1e-5f32);
+            }
+            let region = BoundingBox::new(Vector3::new(-5f32, -8f32, -3f32), Vector3::new(6f32, 2f32, 9f32));
+            let mut found = bvh.query_box(region);
+            found.sort();
+            let expected: Vec<usize> = (0..primitives.len()).filter(|&i| primitives[i].bounds().intersects_box(region)).collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn max_distance_limits_hits() {
+        let bvh = Bvh::new(vec![BoundingSphere::new(Vector3::new(10f32, 0f32, 0f32), 1f32)], SplitStrategy::Median);
+        assert_eq!(bvh.raycast(Vector3::zero(), Vector3::UNIT_X, 20f32).map(|h| h.distance), Some(9f32));
+        assert!(bvh.raycast(Vector3::zero(), Vector3::UNIT_X, 8f32).is_none());
+        assert!(bvh.nearest_within(Vector3::zero(), 8f32).is_none());
+        assert_eq!(bvh.nearest_within(Vector3::zero(), 10f32).map(|h| h.index), Some(0));
+    }
+
+    #[test]
+    fn frustum_query_keeps_visible_primitives() {
+        let primitives = spheres(300);
+        let bvh = Bvh::new(primitives.clone(), SplitStrategy::SurfaceAreaHeuristic);
+        let view       = Matrix::look_at(Vector3::new(0f32, 0f32, 30f32), Vector3::zero(), Vector3::UNIT_Y);
+        let projection = Matrix::perspective_fov(Degrees(40f32), 1f32, 0.1f32, 100f32);
+        let frustum    = BoundingFrustum::new(view * projection);
+        let found = bvh.query_frustum(&frustum);
+        for (i, p) in primitives.iter().enumerate() {
+            if frustum.contains_point(p.center) {
+                assert!(found.contains(&i));
+            }
+        }
+        assert!(found.len() < primitives.len());
+    }
+
+    #[test]
+    fn refit_follows_moved_primitives() {
+        let mut bvh = Bvh::new(spheres(50), SplitStrategy::SurfaceAreaHeuristic);
+        for sphere in bvh.primitives_mut() {
+            sphere.center += Vector3::new(100f32, 0f32, 0f32);
+        }
+        bvh.refit();
+        assert!(bvh.bounds().unwrap().min.x > 75f32);
+        assert!(bvh.query_box(BoundingBox::new(Vector3::new(-30f32, -30f32, -30f32), Vector3::new(30f32, 30f32, 30f32))).is_empty());
+        assert_eq!(bvh.query_box(bvh.bounds().unwrap()).len(), 50);
+        assert!(Bvh::<BoundingSphere>::new(Vec::new(), SplitStrategy::Median).raycast(Vector3::zero(), Vector3::UNIT_X, 1f32).is_none());
+    }
+}
@@ -0,0 +1,368 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::quaternion::Quaternion;
+use super::matrix::Matrix;
+use super::angle::{Radians, Degrees};
+use super::bounding_frustum::BoundingFrustum;
+use super::scalar;
+
+// camera controllers. input sets the target state, update(delta_time) eases
+// the current state towards it with scalar::smooth_damp over smooth_time
+// seconds (0 snaps), and view() builds the view matrix with Matrix::look_at
+// from the current state. right handed, y up, looking down -z at zero yaw.
+
+fn pitch_limit() -> f32 {
+    Radians::from(Degrees(89f32)).0
+}
+
+// direction of a yaw about +y followed by a pitch about the right axis.
+fn direction(yaw: f32, pitch: f32) -> Vector3 {
+    Vector3::new(-yaw.sin() * pitch.cos(), pitch.sin(), -yaw.cos() * pitch.cos())
+}
+
+fn damp(current: f32, target: f32, velocity: &mut f32, smooth_time: f32, delta_time: f32) -> f32 {
+    if smooth_time <= 0f32 {
+        *velocity = 0f32;
+        return target;
+    }
+    scalar::smooth_damp(current, target, velocity, smooth_time, delta_time)
+}
+
+fn damp_vector(current: Vector3, target: Vector3, velocity: &mut Vector3, smooth_time: f32, delta_time: f32) -> Vector3 {
+    if smooth_time <= 0f32 {
+        *velocity = Vector3::zero();
+        return target;
+    }
+    Vector3::smooth_damp(current, target, velocity, smooth_time, delta_time)
+}
+
+// circles a target. positive pitch raises the camera above the target.
+pub struct OrbitCamera {
+    pub target:       Vector3,
+    pub distance:     f32,
+    pub yaw:          Radians,
+    pub pitch:        Radians,
+    pub min_pitch:    Radians,
+    pub max_pitch:    Radians,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub smooth_time:  f32,
+    current_target:   Vector3,
+    current_distance: f32,
+    current_yaw:      f32,
+    current_pitch:    f32,
+    target_velocity:  Vector3,
+    distance_velocity: f32,
+    yaw_velocity:     f32,
+    pitch_velocity:   f32
+}
+
+impl OrbitCamera {
+
+    pub fn new(target: Vector3, distance: f32) -> OrbitCamera {
+        OrbitCamera {
+            target,
+            distance,
+            yaw:               Radians(0f32),
+            pitch:             Radians(0f32),
+            min_pitch:         Radians(-pitch_limit()),
+            max_pitch:         Radians(pitch_limit()),
+            min_distance:      0.01f32,
+            max_distance:      f32::MAX,
+            smooth_time:       0.1f32,
+            current_target:    target,
+            current_distance:  distance,
+            current_yaw:       0f32,
+            current_pitch:     0f32,
+            target_velocity:   Vector3::zero(),
+            distance_velocity: 0f32,
+            yaw_velocity:      0f32,
+            pitch_velocity:    0f32
+        }
+    }
+
+    pub fn rotate(&mut self, yaw: impl Into<Radians>, pitch: impl Into<Radians>) {
+        self.yaw += yaw.into();
+        self.pitch = Radians(scalar::clamp((self.pitch + pitch.into()).0, self.min_pitch.0, self.max_pitch.0));
+    }
+
+    // positive amounts move closer. scales the distance so zoom feels the same
+    // near and far.
+    pub fn zoom(&mut self, amount: f32) {
+        self.distance = scalar::clamp(self.distance * (1f32 - amount), self.min_distance, self.max_distance);
+    }
+
+    // moves the target in the camera's view plane. the view matrix maps world
+    // to camera space, so the camera's axes are read from its inverse.
+    pub fn pan(&mut self, right: f32, up: f32) {
+        let world = Matrix::invert(self.view());
+        self.target += (world.right() * right) + (world.up() * up);
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let smooth_time = self.smooth_time;
+        self.current_target   = damp_vector(self.current_target, self.target, &mut self.target_velocity, smooth_time, delta_time);
+        self.current_distance = damp(self.current_distance, self.distance, &mut self.distance_velocity, smooth_time, delta_time);
+        self.current_yaw      = damp(self.current_yaw, self.yaw.0, &mut self.yaw_velocity, smooth_time, delta_time);
+        self.current_pitch    = damp(self.current_pitch, self.pitch.0, &mut self.pitch_velocity, smooth_time, delta_time);
+    }
+
+    pub fn snap(&mut self) {
+        let smooth_time = self.smooth_time;
+        self.smooth_time = 0f32;
+        self.update(0f32);
+        self.smooth_time = smooth_time;
+    }
+
+    pub fn position(&self) -> Vector3 {
+        self.current_target - (direction(self.current_yaw, -self.current_pitch) * self.current_distance)
+    }
+
+    pub fn view(&self) -> Matrix {
+        Matrix::look_at(self.position(), self.current_target, Vector3::UNIT_Y)
+    }
+
+    pub fn frustum(&self, projection: Matrix) -> BoundingFrustum {
+        BoundingFrustum::new(self.view() * projection)
+    }
+}
+
+// yaw and pitch look with movement relative to the heading.
+pub struct FirstPersonCamera {
+    pub position:      Vector3,
+    pub yaw:           Radians,
+    pub pitch:         Radians,
+    pub smooth_time:   f32,
+    current_position:  Vector3,
+    current_yaw:       f32,
+    current_pitch:     f32,
+    position_velocity: Vector3,
+    yaw_velocity:      f32,
+    pitch_velocity:    f32
+}
+
+impl FirstPersonCamera {
+
+    pub fn new(position: Vector3) -> FirstPersonCamera {
+        FirstPersonCamera {
+            position,
+            yaw:               Radians(0f32),
+            pitch:             Radians(0f32),
+            smooth_time:       0.05f32,
+            current_position:  position,
+            current_yaw:       0f32,
+            current_pitch:     0f32,
+            position_velocity: Vector3::zero(),
+            yaw_velocity:      0f32,
+            pitch_velocity:    0f32
+        }
+    }
+
+    pub fn rotate(&mut self, yaw: impl Into<Radians>, pitch: impl Into<Radians>) {
+        self.yaw += yaw.into();
+        self.pitch = Radians(scalar::clamp((self.pitch + pitch.into()).0, -pitch_limit(), pitch_limit()));
+    }
+
+    // forward and right follow the heading on the ground plane, up is world up.
+    pub fn move_local(&mut self, forward: f32, right: f32, up: f32) {
+        let heading = direction(self.yaw.0, 0f32);
+        let side    = Vector3::cross(heading, Vector3::UNIT_Y);
+        self.position += ((heading * forward) + (side * right)) + (Vector3::UNIT_Y * up);
+    }
+
+    pub fn forward(&self) -> Vector3 {
+        direction(self.current_yaw, self.current_pitch)
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let smooth_time = self.smooth_time;
+        self.current_position = damp_vector(self.current_position, self.position, &mut self.position_velocity, smooth_time, delta_time);
+        self.current_yaw      = damp(self.current_yaw, self.yaw.0, &mut self.yaw_velocity, smooth_time, delta_time);
+        self.current_pitch    = damp(self.current_pitch, self.pitch.0, &mut self.pitch_velocity, smooth_time, delta_time);
+    }
+
+    pub fn snap(&mut self) {
+        let smooth_time = self.smooth_time;
+        self.smooth_time = 0f32;
+        self.update(0f32);
+        self.smooth_time = smooth_time;
+    }
+
+    pub fn view(&self) -> Matrix {
+        Matrix::look_at(self.current_position, self.current_position + self.forward(), Vector3::UNIT_Y)
+    }
+
+    pub fn frustum(&self, projection: Matrix) -> BoundingFrustum {
+        BoundingFrustum::new(self.view() * projection)
+    }
+}
+
+// six degrees of freedom. rotations are about the camera's own axes, so there
+// is no pitch limit and roll is allowed.
+pub struct FlyCamera {
+    pub position:        Vector3,
+    pub orientation:     Quaternion,
+    pub smooth_time:     f32,
+    current_position:    Vector3,
+    current_orientation: Quaternion,
+    position_velocity:   Vector3
+}
+
+impl FlyCamera {
+
+    pub fn new(position: Vector3) -> FlyCamera {
+        FlyCamera {
+            position,
+            orientation:         Quaternion::identity(),
+            smooth_time:         0.1f32,
+            current_position:    position,
+            current_orientation: Quaternion::identity(),
+            position_velocity:   Vector3::zero()
+        }
+    }
+
+    pub fn rotate(&mut self, yaw: impl Into<Radians>, pitch: impl Into<Radians>, roll: impl Into<Radians>) {
+        let rotation = (Quaternion::from_axis_angle(Vector3::UNIT_Y, yaw)
+                     *  Quaternion::from_axis_angle(Vector3::UNIT_X, pitch))
+                     *  Quaternion::from_axis_angle(Vector3::UNIT_Z, roll);
+        self.orientation = (self.orientation * rotation).normalize();
+    }
+
+    pub fn move_local(&mut self, forward: f32, right: f32, up: f32) {
+        let offset = Vector3::new(right, up, -forward);
+        self.position += Vector3::transform_quaternion(offset, self.orientation);
+    }
+
+    pub fn forward(&self) -> Vector3 {
+        Vector3::transform_quaternion(-Vector3::UNIT_Z, self.current_orientation)
+    }
+
+    pub fn up(&self) -> Vector3 {
+        Vector3::transform_quaternion(Vector3::UNIT_Y, self.current_orientation)
+    }
+
+    // orientation eases with a slerp whose rate roughly matches smooth_damp.
+    pub fn update(&mut self, delta_time: f32) {
+        let smooth_time = self.smooth_time;
+        self.current_position = damp_vector(self.current_position, self.position, &mut self.position_velocity, smooth_time, delta_time);
+        let amount = if smooth_time <= 0f32 { 1f32 } else { 1f32 - (-(2f32 * delta_time) / smooth_time).exp() };
+        self.current_orientation = Quaternion::slerp(self.current_orientation, self.orientation, amount).normalize();
+    }
+
+    pub fn snap(&mut self) {
+        let smooth_time = self.smooth_time;
+        self.smooth_time = 0f32;
+        self.update(0f32);
+        self.smooth_time = smooth_time;
+    }
+
+    pub fn view(&self) -> Matrix {
+        Matrix::look_at(self.current_position, self.current_position + self.forward(), self.up())
+    }
+
+    pub fn frustum(&self, projection: Matrix) -> BoundingFrustum {
+        BoundingFrustum::new(self.view() * projection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Degrees};
+    use super::{OrbitCamera, FirstPersonCamera, FlyCamera};
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-4f32
+    }
+
+    #[test]
+    fn orbit_starts_behind_target() {
+        let mut camera = OrbitCamera::new(Vector3::new(1f32, 2f32, 3f32), 5f32);
+        assert!(near(camera.position(), Vector3::new(1f32, 2f32, 8f32)));
+        camera.rotate(Degrees(0f32), Degrees(30f32));
+        camera.snap();
+        assert!(camera.position().y > 2f32);
+        assert!(((camera.position() - camera.target).length() - 5f32).abs() < 1e-4f32);
+        assert!(near(Vector3::transform(camera.target, camera.view()), Vector3::new(0f32, 0f32, -5f32)));
+    }
+
+    #[test]
+    fn orbit_pans_along_screen_axes() {
+        let mut camera = OrbitCamera::new(Vector3::zero(), 5f32);
+        camera.rotate(Degrees(90f32), Degrees(30f32));
+        camera.snap();
+        camera.pan(1f32, 0f32);
+        assert!(near(camera.target, Vector3::new(0f32, 0f32, -1f32)));
+        camera.snap();
+        let before = camera.target;
+        camera.pan(0f32, 1f32);
+        let up = Vector3::transform_normal(camera.target - before, camera.view());
+        assert!(near(up, Vector3::UNIT_Y));
+    }
+
+    #[test]
+    fn orbit_update_eases_towards_target() {
+        let mut camera = OrbitCamera::new(Vector3::zero(), 5f32);
+        camera.zoom(0.5f32);
+        camera.update(0.05f32);
+        let halfway = (camera.position() - camera.target).length();
+        assert!(halfway < 5f32 && halfway > 2.5f32);
+        for _ in 0..200 {
+            camera.update(0.05f32);
+        }
+        assert!(((camera.position() - camera.target).length() - 2.5f32).abs() < 1e-3f32);
+    }
+
+    #[test]
+    fn first_person_moves_along_heading() {
+        let mut camera = FirstPersonCamera::new(Vector3::zero());
+        camera.rotate(Degrees(90f32), Degrees(45f32));
+        camera.snap();
+        camera.move_local(1f32, 0f32, 0f32);
+        assert!(near(camera.position, Vector3::new(-1f32, 0f32, 0f32)));
+        camera.move_local(0f32, 1f32, 1f32);
+        assert!(near(camera.position, Vector3::new(-1f32, 1f32, -1f32)));
+        assert!(camera.forward().y > 0f32);
+    }
+
+    #[test]
+    fn fly_rotates_about_its_own_axes() {
+        let mut camera = FlyCamera::new(Vector3::zero());
+        camera.rotate(Degrees(90f32), Degrees(0f32), Degrees(0f32));
+        camera.snap();
+        assert!(near(camera.forward(), Vector3::new(-1f32, 0f32, 0f32)));
+        camera.rotate(Degrees(0f32), Degrees(0f32), Degrees(90f32));
+        camera.snap();
+        assert!(near(camera.forward(), Vector3::new(-1f32, 0f32, 0f32)));
+        assert!(near(camera.up(), Vector3::new(0f32, 0f32, 1f32)));
+        camera.move_local(2f32, 0f32, 0f32);
+        camera.snap();
+        assert!(near(Vector3::transform(Vector3::new(-3f32, 0f32, 0f32), camera.view()), Vector3::new(0f32, 0f32, -1f32)));
+    }
+}
@@ -0,0 +1,203 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::matrix::Matrix;
+use super::plane::{Plane, IntersectionType};
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::query;
+use super::gjk;
+
+// a segment swept by a sphere, as used by character controllers.
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Capsule {
+    pub start:  Vector3,
+    pub end:    Vector3,
+    pub radius: f32
+}
+
+impl Capsule {
+
+    pub fn new(start: Vector3, end: Vector3, radius: f32) -> Capsule {
+        Capsule {
+            start,
+            end,
+            radius
+        }
+    }
+
+    pub fn to_bounding_box(self) -> BoundingBox {
+        let radius = Vector3::new(self.radius, self.radius, self.radius);
+        BoundingBox::new(Vector3::min(self.start, self.end) - radius, Vector3::max(self.start, self.end) + radius)
+    }
+
+    pub fn to_bounding_sphere(self) -> BoundingSphere {
+        BoundingSphere::new((self.start + self.end) * 0.5f32, (Vector3::distance(self.start, self.end) * 0.5f32) + self.radius)
+    }
+
+    // the radius scales by the largest axis scale of the matrix, so non
+    // uniform scaling gives the capsule enclosing the scaled shape.
+    pub fn transform(capsule: Capsule, matrix: Matrix) -> Capsule {
+        let scale = matrix.right().length().max(matrix.up().length()).max(matrix.backward().length());
+        Capsule {
+            start:  Vector3::transform(capsule.start, matrix),
+            end:    Vector3::transform(capsule.end, matrix),
+            radius: capsule.radius * scale
+        }
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        let center = query::closest_point_on_segment(point, self.start, self.end);
+        query::closest_point_on_sphere(point, BoundingSphere::new(center, self.radius))
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        query::distance_squared_point_segment(point, self.start, self.end) <= self.radius * self.radius
+    }
+
+    // distance along the ray in units of direction, 0 when the ray starts inside.
+    pub fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        if self.contains_point(position) {
+            return Some(0f32);
+        }
+        if let Some(t) = query::ray_cylinder_side(position, direction, self.start, self.end, self.radius) {
+            return Some(t);
+        }
+        let start = BoundingSphere::new(self.start, self.radius).intersects_ray(position, direction);
+        let end   = BoundingSphere::new(self.end, self.radius).intersects_ray(position, direction);
+        match (start, end) {
+            (Some(t1), Some(t2)) => Some(t1.min(t2)),
+            (t1, t2)             => t1.or(t2)
+        }
+    }
+
+    pub fn intersects_capsule(&self, other: Capsule) -> bool {
+        let (p1, p2) = query::closest_points_segment_segment(self.start, self.end, other.start, other.end);
+        let radius = self.radius + other.radius;
+        (p1 - p2).length_squared() <= radius * radius
+    }
+
+    pub fn intersects_sphere(&self, sphere: BoundingSphere) -> bool {
+        let radius = self.radius + sphere.radius;
+        query::distance_squared_point_segment(sphere.center, self.start, self.end) <= radius * radius
+    }
+
+    pub fn intersects_box(&self, bounds: BoundingBox) -> bool {
+        gjk::intersects(self, &bounds)
+    }
+
+    pub fn intersects_plane(&self, plane: Plane) -> IntersectionType {
+        let plane = Plane::normalize(plane);
+        let start = Plane::dot_coordinate(plane, self.start);
+        let end   = Plane::dot_coordinate(plane, self.end);
+        if start.min(end) > self.radius {
+            IntersectionType::front
+        } else if start.max(end) < -self.radius {
+            IntersectionType::back
+        } else {
+            IntersectionType::intersecting
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Matrix, Plane, IntersectionType, BoundingBox, BoundingSphere};
+    use super::Capsule;
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-4f32
+    }
+
+    fn upright() -> Capsule {
+        Capsule::new(Vector3::zero(), Vector3::new(0f32, 4f32, 0f32), 1f32)
+    }
+
+    #[test]
+    fn closest_point_and_contains_point() {
+        let capsule = upright();
+        assert!(near(capsule.closest_point(Vector3::new(3f32, 2f32, 0f32)), Vector3::new(1f32, 2f32, 0f32)));
+        assert!(near(capsule.closest_point(Vector3::new(0f32, 6f32, 0f32)), Vector3::new(0f32, 5f32, 0f32)));
+        assert!(capsule.contains_point(Vector3::new(0.5f32, 4.5f32, 0f32)));
+        assert!(capsule.contains_point(Vector3::new(0f32, -0.9f32, 0f32)));
+        assert!(!capsule.contains_point(Vector3::new(0.9f32, 4.9f32, 0f32)));
+        assert!(!capsule.contains_point(Vector3::new(1.1f32, 2f32, 0f32)));
+    }
+
+    #[test]
+    fn ray_hits_side_and_caps() {
+        let capsule = upright();
+        let side = capsule.intersects_ray(Vector3::new(-5f32, 2f32, 0f32), Vector3::UNIT_X).unwrap();
+        assert!((side - 4f32).abs() < 1e-4f32);
+        let cap = capsule.intersects_ray(Vector3::new(0f32, 10f32, 0f32), -Vector3::UNIT_Y).unwrap();
+        assert!((cap - 5f32).abs() < 1e-4f32);
+        assert_eq!(capsule.intersects_ray(Vector3::new(0f32, 2f32, 0f32), Vector3::UNIT_X), Some(0f32));
+        assert!(capsule.intersects_ray(Vector3::new(-5f32, 10f32, 0f32), Vector3::UNIT_X).is_none());
+        assert!(capsule.intersects_ray(Vector3::new(-5f32, 2f32, 0f32), -Vector3::UNIT_X).is_none());
+    }
+
+    #[test]
+    fn intersects_other_shapes() {
+        let capsule = upright();
+        assert!(capsule.intersects_capsule(Capsule::new(Vector3::new(2f32, 0f32, 0f32), Vector3::new(2f32, 4f32, 0f32), 1.1f32)));
+        assert!(!capsule.intersects_capsule(Capsule::new(Vector3::new(2f32, 0f32, 0f32), Vector3::new(2f32, 4f32, 0f32), 0.9f32)));
+        assert!(capsule.intersects_sphere(BoundingSphere::new(Vector3::new(0f32, -1.5f32, 0f32), 0.6f32)));
+        assert!(!capsule.intersects_sphere(BoundingSphere::new(Vector3::new(0f32, -1.5f32, 0f32), 0.4f32)));
+        assert!(capsule.intersects_box(BoundingBox::new(Vector3::new(0.5f32, 1f32, -1f32), Vector3::new(3f32, 2f32, 1f32))));
+        assert!(!capsule.intersects_box(BoundingBox::new(Vector3::new(1.5f32, 1f32, -1f32), Vector3::new(3f32, 2f32, 1f32))));
+        assert!(!capsule.intersects_box(BoundingBox::new(Vector3::new(0.8f32, 4.8f32, -1f32), Vector3::new(2f32, 6f32, 1f32))));
+    }
+
+    #[test]
+    fn classifies_against_planes() {
+        let capsule = upright();
+        assert!(capsule.intersects_plane(Plane::new(0f32, 1f32, 0f32, 2f32)) == IntersectionType::front);
+        assert!(capsule.intersects_plane(Plane::new(0f32, 1f32, 0f32, -6f32)) == IntersectionType::back);
+        assert!(capsule.intersects_plane(Plane::new(0f32, 1f32, 0f32, -4.5f32)) == IntersectionType::intersecting);
+        assert!(capsule.intersects_plane(Plane::new(0f32, 2f32, 0f32, -9f32)) == IntersectionType::intersecting);
+    }
+
+    #[test]
+    fn bounds_and_transform() {
+        let capsule = upright();
+        let bounds = capsule.to_bounding_box();
+        assert!(near(bounds.min, Vector3::new(-1f32, -1f32, -1f32)));
+        assert!(near(bounds.max, Vector3::new(1f32, 5f32, 1f32)));
+        let sphere = capsule.to_bounding_sphere();
+        assert!(near(sphere.center, Vector3::new(0f32, 2f32, 0f32)));
+        assert!((sphere.radius - 3f32).abs() < 1e-4f32);
+        let matrix = Matrix::mul(Matrix::scale(Vector3::new(2f32, 1f32, 1f32)), Matrix::translation(Vector3::UNIT_X));
+        let moved = Capsule::transform(capsule, matrix);
+        assert!(near(moved.start, Vector3::new(1f32, 0f32, 0f32)));
+        assert!(near(moved.end, Vector3::new(1f32, 4f32, 0f32)));
+        assert!((moved.radius - 2f32).abs() < 1e-4f32);
+    }
+}
@@ -0,0 +1,412 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::ops::{Add, Sub, Mul};
+use std::fmt::{Display, Formatter, Error};
+use super::vector3::Vector3;
+use super::vector4::Vector4;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32
+}
+
+// 8-bit per channel color. packed u32 orders place the first named channel in
+// the most significant byte, i.e. rgba is 0xRRGGBBAA.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PackedColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8
+}
+
+impl Add<Color> for Color {
+    type Output = Color;
+    fn add(self, rhs: Color) -> Color {
+        Color {
+            r: self.r + rhs.r,
+            g: self.g + rhs.g,
+            b: self.b + rhs.b,
+            a: self.a + rhs.a
+        }
+    }
+}
+
+impl Sub<Color> for Color {
+    type Output = Color;
+    fn sub(self, rhs: Color) -> Color {
+        Color {
+            r: self.r - rhs.r,
+            g: self.g - rhs.g,
+            b: self.b - rhs.b,
+            a: self.a - rhs.a
+        }
+    }
+}
+
+impl Mul<Color> for Color {
+    type Output = Color;
+    fn mul(self, rhs: Color) -> Color {
+        Color {
+            r: self.r * rhs.r,
+            g: self.g * rhs.g,
+            b: self.b * rhs.b,
+            a: self.a * rhs.a
+        }
+    }
+}
+
+impl Mul<f32> for Color {
+    type Output = Color;
+    fn mul(self, rhs: f32) -> Color {
+        Color {
+            r: self.r * rhs,
+            g: self.g * rhs,
+            b: self.b * rhs,
+            a: self.a * rhs
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {}, {}, {})", self.r, self.g, self.b, self.a)
+    }
+}
+
+impl Display for PackedColor {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {}, {}, {})", self.r, self.g, self.b, self.a)
+    }
+}
+
+impl From<Vector3> for Color {
+    fn from(vector: Vector3) -> Color {
+        Color::new(vector.x, vector.y, vector.z, 1f32)
+    }
+}
+
+impl From<Vector4> for Color {
+    fn from(vector: Vector4) -> Color {
+        Color::new(vector.x, vector.y, vector.z, vector.w)
+    }
+}
+
+impl From<Color> for Vector3 {
+    fn from(color: Color) -> Vector3 {
+        color.to_vector3()
+    }
+}
+
+impl From<Color> for Vector4 {
+    fn from(color: Color) -> Vector4 {
+        color.to_vector4()
+    }
+}
+
+impl From<PackedColor> for Color {
+    fn from(color: PackedColor) -> Color {
+        color.to_color()
+    }
+}
+
+impl From<Color> for PackedColor {
+    fn from(color: Color) -> PackedColor {
+        color.pack()
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045f32 {
+        value / 12.92f32
+    } else {
+        ((value + 0.055f32) / 1.055f32).powf(2.4f32)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308f32 {
+        value * 12.92f32
+    } else {
+        (1.055f32 * value.powf(1f32 / 2.4f32)) - 0.055f32
+    }
+}
+
+fn hue_to_rgb(hue: f32, chroma: f32, m: f32) -> (f32, f32, f32) {
+    let h = (hue.rem_euclid(360f32)) / 60f32;
+    let x = chroma * (1f32 - ((h % 2f32) - 1f32).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0f32),
+        1 => (x, chroma, 0f32),
+        2 => (0f32, chroma, x),
+        3 => (0f32, x, chroma),
+        4 => (x, 0f32, chroma),
+        _ => (chroma, 0f32, x)
+    };
+    (r + m, g + m, b + m)
+}
+
+impl Color {
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color {
+            r,
+            g,
+            b,
+            a
+        }
+    }
+
+    pub fn transparent() -> Color { Color::new(0f32, 0f32, 0f32, 0f32) }
+    pub fn black      () -> Color { Color::new(0f32, 0f32, 0f32, 1f32) }
+    pub fn white      () -> Color { Color::new(1f32, 1f32, 1f32, 1f32) }
+    pub fn red        () -> Color { Color::new(1f32, 0f32, 0f32, 1f32) }
+    pub fn green      () -> Color { Color::new(0f32, 1f32, 0f32, 1f32) }
+    pub fn blue       () -> Color { Color::new(0f32, 0f32, 1f32, 1f32) }
+
+    pub fn to_vector3(self) -> Vector3 {
+        Vector3::new(self.r, self.g, self.b)
+    }
+
+    pub fn to_vector4(self) -> Vector4 {
+        Vector4::new(self.r, self.g, self.b, self.a)
+    }
+
+    pub fn to_linear(self) -> Color {
+        Color::new(srgb_to_linear(self.r), srgb_to_linear(self.g), srgb_to_linear(self.b), self.a)
+    }
+
+    pub fn to_srgb(self) -> Color {
+        Color::new(linear_to_srgb(self.r), linear_to_srgb(self.g), linear_to_srgb(self.b), self.a)
+    }
+
+    // hue in degrees [0, 360), saturation and value in [0, 1].
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, alpha: f32) -> Color {
+        let chroma = value * saturation;
+        let (r, g, b) = hue_to_rgb(hue, chroma, value - chroma);
+        Color::new(r, g, b, alpha)
+    }
+
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max   = self.r.max(self.g).max(self.b);
+        let min   = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let saturation = if max > 0f32 { delta / max } else { 0f32 };
+        (self.hue(max, delta), saturation, max)
+    }
+
+    // hue in degrees [0, 360), saturation and lightness in [0, 1].
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Color {
+        let chroma = (1f32 - ((2f32 * lightness) - 1f32).abs()) * saturation;
+        let (r, g, b) = hue_to_rgb(hue, chroma, lightness - (chroma * 0.5f32));
+        Color::new(r, g, b, alpha)
+    }
+
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let max   = self.r.max(self.g).max(self.b);
+        let min   = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let lightness  = (max + min) * 0.5f32;
+        let saturation = if delta > 0f32 { delta / (1f32 - ((2f32 * lightness) - 1f32).abs()) } else { 0f32 };
+        (self.hue(max, delta), saturation, lightness)
+    }
+
+    fn hue(&self, max: f32, delta: f32) -> f32 {
+        if delta <= 0f32 {
+            return 0f32;
+        }
+        let hue = if max == self.r {
+            60f32 * ((self.g - self.b) / delta)
+        } else if max == self.g {
+            60f32 * (((self.b - self.r) / delta) + 2f32)
+        } else {
+            60f32 * (((self.r - self.g) / delta) + 4f32)
+        };
+        hue.rem_euclid(360f32)
+    }
+
+    pub fn premultiply(&self) -> Color {
+        Color::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    pub fn unpremultiply(&self) -> Color {
+        if self.a <= 0f32 {
+            return Color::transparent();
+        }
+        let num = 1f32 / self.a;
+        Color::new(self.r * num, self.g * num, self.b * num, self.a)
+    }
+
+    pub fn lerp(value1: Color, value2: Color, amount: f32) -> Color {
+        Color {
+            r: value1.r + ((value2.r - value1.r) * amount),
+            g: value1.g + ((value2.g - value1.g) * amount),
+            b: value1.b + ((value2.b - value1.b) * amount),
+            a: value1.a + ((value2.a - value1.a) * amount)
+        }
+    }
+
+    pub fn pack(&self) -> PackedColor {
+        let channel = |value: f32| (value.clamp(0f32, 1f32) * 255f32).round() as u8;
+        PackedColor::new(channel(self.r), channel(self.g), channel(self.b), channel(self.a))
+    }
+
+    pub fn to_rgba(self) -> u32 { self.pack().to_rgba() }
+    pub fn to_bgra(self) -> u32 { self.pack().to_bgra() }
+    pub fn to_argb(self) -> u32 { self.pack().to_argb() }
+
+    pub fn from_rgba(value: u32) -> Color { PackedColor::from_rgba(value).to_color() }
+    pub fn from_bgra(value: u32) -> Color { PackedColor::from_bgra(value).to_color() }
+    pub fn from_argb(value: u32) -> Color { PackedColor::from_argb(value).to_color() }
+}
+
+impl PackedColor {
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> PackedColor {
+        PackedColor {
+            r,
+            g,
+            b,
+            a
+        }
+    }
+
+    pub fn to_color(self) -> Color {
+        let num = 1f32 / 255f32;
+        Color::new(self.r as f32 * num, self.g as f32 * num, self.b as f32 * num, self.a as f32 * num)
+    }
+
+    pub fn to_rgba(self) -> u32 {
+        u32::from_be_bytes([self.r, self.g, self.b, self.a])
+    }
+
+    pub fn to_bgra(self) -> u32 {
+        u32::from_be_bytes([self.b, self.g, self.r, self.a])
+    }
+
+    pub fn to_argb(self) -> u32 {
+        u32::from_be_bytes([self.a, self.r, self.g, self.b])
+    }
+
+    pub fn from_rgba(value: u32) -> PackedColor {
+        let [r, g, b, a] = value.to_be_bytes();
+        PackedColor::new(r, g, b, a)
+    }
+
+    pub fn from_bgra(value: u32) -> PackedColor {
+        let [b, g, r, a] = value.to_be_bytes();
+        PackedColor::new(r, g, b, a)
+    }
+
+    pub fn from_argb(value: u32) -> PackedColor {
+        let [a, r, g, b] = value.to_be_bytes();
+        PackedColor::new(r, g, b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Color, PackedColor};
+
+    fn near(a: Color, b: Color) -> bool {
+        (a.r - b.r).abs() < 1e-4f32 && (a.g - b.g).abs() < 1e-4f32 && (a.b - b.b).abs() < 1e-4f32 && (a.a - b.a).abs() < 1e-4f32
+    }
+
+    fn samples() -> Vec<Color> {
+        vec![Color::black(), Color::white(), Color::red(), Color::green(), Color::blue(),
+             Color::new(1f32, 1f32, 0f32, 1f32), Color::new(0f32, 1f32, 1f32, 1f32), Color::new(1f32, 0f32, 1f32, 1f32),
+             Color::new(0.5f32, 0.5f32, 0.5f32, 1f32), Color::new(0.2f32, 0.2f32, 0.2f32, 0.5f32),
+             Color::new(0.9f32, 0.1f32, 0.4f32, 1f32), Color::new(0.3f32, 0.6f32, 0.05f32, 0.25f32)]
+    }
+
+    #[test]
+    fn linear_and_srgb_round_trip() {
+        for color in samples() {
+            assert!(near(color.to_linear().to_srgb(), color));
+            assert!(near(color.to_srgb().to_linear(), color));
+        }
+        assert!(near(Color::new(0.5f32, 0.5f32, 0.5f32, 1f32).to_linear(), Color::new(0.21404f32, 0.21404f32, 0.21404f32, 1f32)));
+        assert!(near(Color::new(0.02f32, 0.02f32, 0.02f32, 1f32).to_linear(), Color::new(0.02f32 / 12.92f32, 0.02f32 / 12.92f32, 0.02f32 / 12.92f32, 1f32)));
+    }
+
+    #[test]
+    fn hsv_and_hsl_round_trip() {
+        for color in samples() {
+            let (h, s, v) = color.to_hsv();
+            assert!(near(Color::from_hsv(h, s, v, color.a), color));
+            let (h, s, l) = color.to_hsl();
+            assert!(near(Color::from_hsl(h, s, l, color.a), color));
+        }
+        assert!(Color::green().to_hsv() == (120f32, 1f32, 1f32));
+        assert!(Color::blue().to_hsl() == (240f32, 1f32, 0.5f32));
+        assert!(Color::new(0.5f32, 0.5f32, 0.5f32, 1f32).to_hsv() == (0f32, 0f32, 0.5f32));
+        assert!(Color::new(0.25f32, 0.25f32, 0.25f32, 1f32).to_hsl() == (0f32, 0f32, 0.25f32));
+    }
+
+    #[test]
+    fn hue_wraps_around() {
+        assert!(near(Color::from_hsv(-60f32, 1f32, 1f32, 1f32), Color::from_hsv(300f32, 1f32, 1f32, 1f32)));
+        assert!(near(Color::from_hsv(420f32, 1f32, 1f32, 1f32), Color::new(1f32, 1f32, 0f32, 1f32)));
+        assert!(near(Color::from_hsl(360f32, 1f32, 0.5f32, 1f32), Color::red()));
+        let (hue, _, _) = Color::new(1f32, 0f32, 0.1f32, 1f32).to_hsv();
+        assert!((hue - 354f32).abs() < 1e-3f32);
+        assert!(near(Color::from_hsv(hue, 1f32, 1f32, 1f32), Color::new(1f32, 0f32, 0.1f32, 1f32)));
+    }
+
+    #[test]
+    fn packed_byte_orders() {
+        let color = PackedColor::new(0x11, 0x22, 0x33, 0x44);
+        assert_eq!(color.to_rgba(), 0x11223344);
+        assert_eq!(color.to_bgra(), 0x33221144);
+        assert_eq!(color.to_argb(), 0x44112233);
+        assert!(PackedColor::from_rgba(0x11223344) == color);
+        assert!(PackedColor::from_bgra(0x33221144) == color);
+        assert!(PackedColor::from_argb(0x44112233) == color);
+        assert_eq!(Color::red().to_rgba(), 0xff0000ff);
+        assert_eq!(Color::red().to_bgra(), 0x0000ffff);
+        assert_eq!(Color::red().to_argb(), 0xffff0000);
+        assert!(near(Color::from_argb(0x80ff0000), Color::new(1f32, 0f32, 0f32, 128f32 / 255f32)));
+        assert!(near(Color::from_bgra(0x0000ffff), Color::red()));
+        assert!(Color::new(1.5f32, -0.5f32, 0.5f32, 1f32).pack() == PackedColor::new(255, 0, 128, 255));
+    }
+
+    #[test]
+    fn premultiply_and_lerp() {
+        let color = Color::new(0.8f32, 0.4f32, 0.2f32, 0.5f32);
+        assert!(near(color.premultiply(), Color::new(0.4f32, 0.2f32, 0.1f32, 0.5f32)));
+        assert!(near(color.premultiply().unpremultiply(), color));
+        assert!(near(Color::new(1f32, 1f32, 1f32, 0f32).unpremultiply(), Color::transparent()));
+        assert!(near(Color::lerp(Color::black(), Color::white(), 0.25f32), Color::new(0.25f32, 0.25f32, 0.25f32, 1f32)));
+    }
+}
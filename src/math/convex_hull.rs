@@ -0,0 +1,366 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::collections::HashMap;
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::plane::Plane;
+
+// indices of the hull vertices in counter clockwise order, starting from the
+// lowest x. collinear points along hull edges are dropped.
+pub fn convex_hull_2d(points: &[Vector2]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| points[a].x.total_cmp(&points[b].x).then(points[a].y.total_cmp(&points[b].y)));
+    order.dedup_by(|a, b| points[*a].x == points[*b].x && points[*a].y == points[*b].y);
+    if order.len() < 3 {
+        return order;
+    }
+    let cross = |o: usize, a: usize, b: usize| {
+        let (o, a, b) = (points[o], points[a], points[b]);
+        ((a.x - o.x) * (b.y - o.y)) - ((a.y - o.y) * (b.x - o.x))
+    };
+    let mut hull: Vec<usize> = Vec::with_capacity(order.len() * 2);
+    for &i in order.iter() {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], i) <= 0f32 {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+    let lower = hull.len() + 1;
+    for &i in order.iter().rev().skip(1) {
+        while hull.len() >= lower && cross(hull[hull.len() - 2], hull[hull.len() - 1], i) <= 0f32 {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+    hull.pop();
+    hull
+}
+
+// triangle mesh of a 3d hull. triangles wind counter clockwise seen from
+// outside, and planes[i] is the outward facing plane of triangles[i].
+pub struct ConvexHull {
+    pub vertices:  Vec<Vector3>,
+    pub triangles: Vec<[usize; 3]>,
+    pub planes:    Vec<Plane>
+}
+
+impl ConvexHull {
+
+    // the hull vertex furthest along direction.
+    pub fn support(&self, direction: Vector3) -> Vector3 {
+        let mut best      = self.vertices[0];
+        let mut best_dot  = Vector3::dot(best, direction);
+        for vertex in &self.vertices[1..] {
+            let dot = Vector3::dot(*vertex, direction);
+            if dot > best_dot {
+                best     = *vertex;
+                best_dot = dot;
+            }
+        }
+        best
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes.iter().all(|plane| Plane::dot_coordinate(*plane, point) <= 0f32)
+    }
+}
+
+// face planes are kept in f64. differences and products of f32 coordinates
+// are exact there, so thin faces between nearly collinear points still get
+// accurate normals and the visibility tests stay consistent.
+struct Face {
+    vertices: [usize; 3],
+    normal:   [f64; 3],
+    offset:   f64,
+    outside:  Vec<usize>,
+    alive:    bool
+}
+
+impl Face {
+    fn new(points: &[Vector3], vertices: [usize; 3]) -> Face {
+        let (a, b, c) = (wide(points[vertices[0]]), wide(points[vertices[1]]), wide(points[vertices[2]]));
+        let cross  = wide_cross(wide_sub(b, a), wide_sub(c, a));
+        let length = wide_dot(cross, cross).sqrt();
+        let normal = if length > 0f64 { [cross[0] / length, cross[1] / length, cross[2] / length] } else { [0f64; 3] };
+        Face { vertices, normal, offset: wide_dot(normal, a), outside: Vec::new(), alive: true }
+    }
+
+    fn distance(&self, point: Vector3) -> f64 {
+        wide_dot(self.normal, wide(point)) - self.offset
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let v = self.vertices;
+        [(v[0], v[1]), (v[1], v[2]), (v[2], v[0])]
+    }
+}
+
+// quickhull. none when the points are fewer than four or lie in a plane.
+pub fn convex_hull_3d(points: &[Vector3]) -> Option<ConvexHull> {
+    if points.len() < 4 {
+        return None;
+    }
+    let mut scale = Vector3::zero();
+    for point in points {
+        scale = Vector3::max(scale, point.abs());
+    }
+    let epsilon = (3f32 * f32::EPSILON) * ((scale.x + scale.y) + scale.z).max(1f32);
+    // coplanarity tolerance for the f64 face planes.
+    let tolerance = 1e-10f64 * f64::from(((scale.x + scale.y) + scale.z).max(1f32));
+
+    // initial tetrahedron from extreme points.
+    let mut extremes = [0usize; 6];
+    for (i, point) in points.iter().enumerate() {
+        for axis in 0..3 {
+            if point[axis] < points[extremes[axis * 2]][axis]     { extremes[axis * 2]     = i; }
+            if point[axis] > points[extremes[axis * 2 + 1]][axis] { extremes[axis * 2 + 1] = i; }
+        }
+    }
+    let (mut i0, mut i1, mut best) = (0, 0, -1f32);
+    for &a in &extremes {
+        for &b in &extremes {
+            let distance = (points[a] - points[b]).length_squared();
+            if distance > best {
+                best = distance;
+                i0   = a;
+                i1   = b;
+            }
+        }
+    }
+    if best.sqrt() <= epsilon {
+        return None;
+    }
+    let line = (points[i1] - points[i0]).normalize();
+    let (i2, distance) = farthest(points, |p| Vector3::cross(p - points[i0], line).length());
+    if distance <= epsilon {
+        return None;
+    }
+    let normal = Vector3::cross(points[i1] - points[i0], points[i2] - points[i0]).normalize();
+    let (i3, distance) = farthest(points, |p| Vector3::dot(p - points[i0], normal).abs());
+    if distance <= epsilon {
+        return None;
+    }
+
+    let mut faces: Vec<Face> = Vec::new();
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    let centroid = (((points[i0] + points[i1]) + points[i2]) + points[i3]) * 0.25f32;
+    for triangle in &[[i0, i1, i2], [i0, i3, i1], [i1, i3, i2], [i2, i3, i0]] {
+        let mut face = Face::new(points, *triangle);
+        if face.distance(centroid) > 0f64 {
+            face = Face::new(points, [triangle[0], triangle[2], triangle[1]]);
+        }
+        add_face(&mut faces, &mut edges, face);
+    }
+    for (i, point) in points.iter().enumerate() {
+        if i == i0 || i == i1 || i == i2 || i == i3 {
+            continue;
+        }
+        if let Some(face) = faces.iter_mut().find(|face| face.distance(*point) > tolerance) {
+            face.outside.push(i);
+        }
+    }
+
+    while let Some(start) = faces.iter().position(|face| face.alive && !face.outside.is_empty()) {
+        let eye = *faces[start].outside.iter().max_by(|&&a, &&b| {
+            faces[start].distance(points[a]).total_cmp(&faces[start].distance(points[b]))
+        }).unwrap();
+        let eye_point = points[eye];
+
+        // flood the faces visible from the eye, collecting the horizon.
+        let mut visible = vec![start];
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        faces[start].alive = false;
+        let mut i = 0;
+        while i < visible.len() {
+            for (a, b) in faces[visible[i]].edges().iter().cloned() {
+                let neighbour = edges[&(b, a)];
+                if !faces[neighbour].alive {
+                    continue;
+                }
+                // a neighbour the eye is coplanar with and in line with the
+                // shared edge is replaced too, rather than leaving a zero area
+                // face between them.
+                let distance = faces[neighbour].distance(eye_point);
+                if distance > tolerance || (distance > -tolerance && collinear(points[a], points[b], eye_point, tolerance)) {
+                    faces[neighbour].alive = false;
+                    visible.push(neighbour);
+                } else {
+                    horizon.push((a, b));
+                }
+            }
+            i += 1;
+        }
+
+        let mut orphans: Vec<usize> = Vec::new();
+        for &face in &visible {
+            for edge in faces[face].edges().iter() {
+                edges.remove(edge);
+            }
+            orphans.append(&mut faces[face].outside);
+        }
+        let first = faces.len();
+        for (a, b) in horizon {
+            add_face(&mut faces, &mut edges, Face::new(points, [a, b, eye]));
+        }
+        for point in orphans {
+            if point == eye {
+                continue;
+            }
+            if let Some(face) = faces[first..].iter_mut().find(|face| face.distance(points[point]) > tolerance) {
+                face.outside.push(point);
+            }
+        }
+    }
+
+    let mut remap: HashMap<usize, usize> = HashMap::new();
+    let mut hull = ConvexHull { vertices: Vec::new(), triangles: Vec::new(), planes: Vec::new() };
+    for face in faces.iter().filter(|face| face.alive) {
+        let mut triangle = [0usize; 3];
+        for (corner, vertex) in triangle.iter_mut().zip(face.vertices.iter()) {
+            *corner = *remap.entry(*vertex).or_insert_with(|| {
+                hull.vertices.push(points[*vertex]);
+                hull.vertices.len() - 1
+            });
+        }
+        hull.triangles.push(triangle);
+        hull.planes.push(Plane::new(face.normal[0] as f32, face.normal[1] as f32, face.normal[2] as f32, -face.offset as f32));
+    }
+    Some(hull)
+}
+
+fn add_face(faces: &mut Vec<Face>, edges: &mut HashMap<(usize, usize), usize>, face: Face) {
+    for edge in face.edges().iter() {
+        edges.insert(*edge, faces.len());
+    }
+    faces.push(face);
+}
+
+fn farthest<F: Fn(Vector3) -> f32>(points: &[Vector3], distance: F) -> (usize, f32) {
+    let mut best = (0, -1f32);
+    for (i, point) in points.iter().enumerate() {
+        let d = distance(*point);
+        if d > best.1 {
+            best = (i, d);
+        }
+    }
+    best
+}
+
+// whether point lies on the line through a and b, within tolerance.
+fn collinear(a: Vector3, b: Vector3, point: Vector3, tolerance: f64) -> bool {
+    let line  = wide_sub(wide(b), wide(a));
+    let cross = wide_cross(line, wide_sub(wide(point), wide(a)));
+    wide_dot(cross, cross) <= (tolerance * tolerance) * wide_dot(line, line)
+}
+
+fn wide(value: Vector3) -> [f64; 3] {
+    [f64::from(value.x), f64::from(value.y), f64::from(value.z)]
+}
+
+fn wide_sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn wide_cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [(a[1] * b[2]) - (a[2] * b[1]), (a[2] * b[0]) - (a[0] * b[2]), (a[0] * b[1]) - (a[1] * b[0])]
+}
+
+fn wide_dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] * b[0]) + (a[1] * b[1])) + (a[2] * b[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector2, Vector3, Plane};
+    use super::{convex_hull_2d, convex_hull_3d};
+
+    fn scatter(count: usize) -> Vec<Vector3> {
+        (0..count).map(|i| {
+            let t = i as f32;
+            Vector3::new((t * 7.31f32) % 10f32 - 5f32, (t * 3.17f32) % 10f32 - 5f32, (t * 1.93f32) % 10f32 - 5f32)
+        }).collect()
+    }
+
+    #[test]
+    fn hull_2d_of_a_square_with_inner_and_edge_points() {
+        let points = vec![
+            Vector2::new(0f32, 0f32), Vector2::new(1f32, 1f32), Vector2::new(2f32, 0f32), Vector2::new(2f32, 2f32),
+            Vector2::new(1f32, 0f32), Vector2::new(0f32, 2f32), Vector2::new(0.5f32, 1.5f32), Vector2::new(2f32, 2f32)];
+        assert_eq!(convex_hull_2d(&points), vec![0, 2, 3, 5]);
+        assert_eq!(convex_hull_2d(&points[..2]).len(), 2);
+    }
+
+    #[test]
+    fn hull_2d_encloses_every_point() {
+        let points: Vec<Vector2> = scatter(200).iter().map(|p| Vector2::new(p.x, p.y)).collect();
+        let hull = convex_hull_2d(&points);
+        for i in 0..hull.len() {
+            let (a, b) = (points[hull[i]], points[hull[(i + 1) % hull.len()]]);
+            for p in &points {
+                assert!(((b.x - a.x) * (p.y - a.y)) - ((b.y - a.y) * (p.x - a.x)) >= -1e-4f32);
+            }
+        }
+    }
+
+    #[test]
+    fn hull_3d_of_a_cube_with_inner_points() {
+        let mut points: Vec<Vector3> = (0..8).map(|i| Vector3::new(
+            if i & 1 == 0 { -1f32 } else { 1f32 },
+            if i & 2 == 0 { -1f32 } else { 1f32 },
+            if i & 4 == 0 { -1f32 } else { 1f32 })).collect();
+        points.extend(scatter(50).iter().map(|p| *p * 0.15f32));
+        let hull = convex_hull_3d(&points).unwrap();
+        assert_eq!(hull.vertices.len(), 8);
+        assert_eq!(hull.triangles.len(), 12);
+        assert!(hull.contains_point(Vector3::new(0.9f32, -0.9f32, 0.5f32)));
+        assert!(!hull.contains_point(Vector3::new(1.1f32, 0f32, 0f32)));
+        assert_eq!(hull.support(Vector3::new(1f32, 2f32, -3f32)).to_array(), [1f32, 1f32, -1f32]);
+    }
+
+    #[test]
+    fn hull_3d_encloses_every_point() {
+        let points = scatter(300);
+        let hull = convex_hull_3d(&points).unwrap();
+        // closed: every edge is shared by exactly two triangles, v - e + f = 2.
+        let edges = hull.triangles.len() * 3 / 2;
+        assert_eq!((hull.vertices.len() + hull.triangles.len()) - edges, 2);
+        let centroid = hull.vertices.iter().fold(Vector3::zero(), |sum, v| sum + *v) * (1f32 / hull.vertices.len() as f32);
+        for plane in &hull.planes {
+            assert!(Plane::dot_coordinate(*plane, centroid) < 0f32);
+            assert!(points.iter().all(|p| Plane::dot_coordinate(*plane, *p) <= 1e-4f32));
+        }
+    }
+
+    #[test]
+    fn hull_3d_of_flat_points_is_none() {
+        let flat: Vec<Vector3> = scatter(20).iter().map(|p| Vector3::new(p.x, p.y, 0f32)).collect();
+        assert!(convex_hull_3d(&flat).is_none());
+        assert!(convex_hull_3d(&flat[..3]).is_none());
+    }
+}
@@ -0,0 +1,508 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::ops::{Add, Sub, Mul};
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::scalar;
+
+const SAMPLES_PER_SEGMENT: usize = 32;
+const MAX_SUBDIVISION_DEPTH: usize = 16;
+
+pub trait CurvePoint: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> {
+    fn zero() -> Self;
+    fn dot(value1: Self, value2: Self) -> f32;
+    fn hermite(value1: Self, tangent1: Self, value2: Self, tangent2: Self, amount: f32) -> Self;
+    fn hermite_derivative(value1: Self, tangent1: Self, value2: Self, tangent2: Self, amount: f32) -> Self;
+    fn length(&self) -> f32 {
+        Self::dot(*self, *self).sqrt()
+    }
+}
+
+impl CurvePoint for Vector2 {
+    fn zero() -> Vector2 {
+        Vector2::zero()
+    }
+    fn dot(value1: Vector2, value2: Vector2) -> f32 {
+        (value1.x * value2.x) + (value1.y * value2.y)
+    }
+    fn hermite(value1: Vector2, tangent1: Vector2, value2: Vector2, tangent2: Vector2, amount: f32) -> Vector2 {
+        Vector2::hermite(value1, tangent1, value2, tangent2, amount)
+    }
+    fn hermite_derivative(value1: Vector2, tangent1: Vector2, value2: Vector2, tangent2: Vector2, amount: f32) -> Vector2 {
+        Vector2::new(
+            scalar::hermite_derivative(value1.x, tangent1.x, value2.x, tangent2.x, amount),
+            scalar::hermite_derivative(value1.y, tangent1.y, value2.y, tangent2.y, amount))
+    }
+}
+
+impl CurvePoint for Vector3 {
+    fn zero() -> Vector3 {
+        Vector3::zero()
+    }
+    fn dot(value1: Vector3, value2: Vector3) -> f32 {
+        Vector3::dot(value1, value2)
+    }
+    fn hermite(value1: Vector3, tangent1: Vector3, value2: Vector3, tangent2: Vector3, amount: f32) -> Vector3 {
+        Vector3::hermite(value1, tangent1, value2, tangent2, amount)
+    }
+    fn hermite_derivative(value1: Vector3, tangent1: Vector3, value2: Vector3, tangent2: Vector3, amount: f32) -> Vector3 {
+        Vector3::new(
+            scalar::hermite_derivative(value1.x, tangent1.x, value2.x, tangent2.x, amount),
+            scalar::hermite_derivative(value1.y, tangent1.y, value2.y, tangent2.y, amount),
+            scalar::hermite_derivative(value1.z, tangent1.z, value2.z, tangent2.z, amount))
+    }
+}
+
+// curves are evaluated over a global parameter t in [0, 1] spanning all segments.
+// derivatives are taken with respect to this global parameter.
+pub trait Curve<T: CurvePoint> {
+
+    fn segment_count(&self) -> usize;
+
+    fn evaluate(&self, t: f32) -> T;
+
+    fn derivative(&self, t: f32) -> T;
+
+    fn tangent(&self, t: f32) -> T {
+        let derivative = self.derivative(t);
+        let length = derivative.length();
+        if length > 0f32 { derivative * (1f32 / length) } else { derivative }
+    }
+
+    fn arc_length(&self) -> f32 {
+        ArcLengthTable::new(self, self.segment_count().max(1) * SAMPLES_PER_SEGMENT).length()
+    }
+
+    fn closest_parameter(&self, point: T) -> f32 {
+        let samples  = self.segment_count().max(1) * SAMPLES_PER_SEGMENT;
+        let distance = |t: f32| { let d = self.evaluate(t) - point; T::dot(d, d) };
+        let mut best = 0f32;
+        let mut best_distance = distance(0f32);
+        for i in 1..(samples + 1) {
+            let t = i as f32 / samples as f32;
+            let d = distance(t);
+            if d < best_distance {
+                best = t;
+                best_distance = d;
+            }
+        }
+        let step = 1f32 / samples as f32;
+        let mut low  = (best - step).max(0f32);
+        let mut high = (best + step).min(1f32);
+        for _ in 0..32 {
+            let m1 = low  + ((high - low) / 3f32);
+            let m2 = high - ((high - low) / 3f32);
+            if distance(m1) < distance(m2) { high = m2; } else { low = m1; }
+        }
+        (low + high) * 0.5f32
+    }
+
+    fn closest_point(&self, point: T) -> T {
+        self.evaluate(self.closest_parameter(point))
+    }
+
+    fn subdivide(&self, tolerance: f32) -> Vec<T> {
+        let mut points = vec![self.evaluate(0f32)];
+        let count = self.segment_count().max(1);
+        for i in 0..count {
+            let t0 = i as f32 / count as f32;
+            let t1 = (i + 1) as f32 / count as f32;
+            subdivide_range(self, t0, t1, tolerance, 0, &mut points);
+        }
+        points
+    }
+}
+
+fn subdivide_range<T: CurvePoint, C: Curve<T> + ?Sized>(curve: &C, t0: f32, t1: f32, tolerance: f32, depth: usize, points: &mut Vec<T>) {
+    let tm    = (t0 + t1) * 0.5f32;
+    let start = curve.evaluate(t0);
+    let end   = curve.evaluate(t1);
+    let mid   = curve.evaluate(tm);
+    let chord = (start + end) * 0.5f32;
+    if depth < MAX_SUBDIVISION_DEPTH && (mid - chord).length() > tolerance {
+        subdivide_range(curve, t0, tm, tolerance, depth + 1, points);
+        subdivide_range(curve, tm, t1, tolerance, depth + 1, points);
+    } else {
+        points.push(end);
+    }
+}
+
+pub struct ArcLengthTable {
+    parameters: Vec<f32>,
+    lengths:    Vec<f32>
+}
+
+impl ArcLengthTable {
+
+    pub fn new<T: CurvePoint, C: Curve<T> + ?Sized>(curve: &C, samples: usize) -> ArcLengthTable {
+        let samples = samples.max(1);
+        let mut parameters = vec![0f32];
+        let mut lengths    = vec![0f32];
+        let mut previous   = curve.evaluate(0f32);
+        let mut total      = 0f32;
+        for i in 1..(samples + 1) {
+            let t = i as f32 / samples as f32;
+            let current = curve.evaluate(t);
+            total += (current - previous).length();
+            parameters.push(t);
+            lengths.push(total);
+            previous = current;
+        }
+        ArcLengthTable {
+            parameters,
+            lengths
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    // maps a distance along the curve to its curve parameter, for constant speed motion.
+    pub fn parameter(&self, distance: f32) -> f32 {
+        if distance <= 0f32 {
+            return 0f32;
+        }
+        if distance >= self.length() {
+            return 1f32;
+        }
+        let index = match self.lengths.binary_search_by(|length| length.total_cmp(&distance)) {
+            Ok(index)  => return self.parameters[index],
+            Err(index) => index
+        };
+        let l0 = self.lengths[index - 1];
+        let l1 = self.lengths[index];
+        let t0 = self.parameters[index - 1];
+        let t1 = self.parameters[index];
+        t0 + ((t1 - t0) * ((distance - l0) / (l1 - l0)))
+    }
+}
+
+fn segment(t: f32, count: usize) -> (usize, f32) {
+    let t = t.clamp(0f32, 1f32) * count as f32;
+    let index = (t.floor() as usize).min(count - 1);
+    (index, t - index as f32)
+}
+
+#[derive(Copy, Clone)]
+pub struct CubicBezier<T: CurvePoint> {
+    pub p0: T,
+    pub p1: T,
+    pub p2: T,
+    pub p3: T
+}
+
+impl<T: CurvePoint> CubicBezier<T> {
+    pub fn new(p0: T, p1: T, p2: T, p3: T) -> CubicBezier<T> {
+        CubicBezier {
+            p0,
+            p1,
+            p2,
+            p3
+        }
+    }
+}
+
+impl<T: CurvePoint> Curve<T> for CubicBezier<T> {
+
+    fn segment_count(&self) -> usize {
+        1
+    }
+
+    fn evaluate(&self, t: f32) -> T {
+        let t = t.clamp(0f32, 1f32);
+        let u = 1f32 - t;
+        (((self.p0 * (u * u * u)) + (self.p1 * (3f32 * u * u * t))) + (self.p2 * (3f32 * u * t * t))) + (self.p3 * (t * t * t))
+    }
+
+    fn derivative(&self, t: f32) -> T {
+        let t = t.clamp(0f32, 1f32);
+        let u = 1f32 - t;
+        (((self.p1 - self.p0) * (3f32 * u * u)) + ((self.p2 - self.p1) * (6f32 * u * t))) + ((self.p3 - self.p2) * (3f32 * t * t))
+    }
+}
+
+#[derive(Clone)]
+pub struct HermiteSpline<T: CurvePoint> {
+    pub points:   Vec<T>,
+    pub tangents: Vec<T>
+}
+
+impl<T: CurvePoint> HermiteSpline<T> {
+    pub fn new(points: Vec<T>, tangents: Vec<T>) -> HermiteSpline<T> {
+        HermiteSpline {
+            points,
+            tangents
+        }
+    }
+}
+
+impl<T: CurvePoint> Curve<T> for HermiteSpline<T> {
+
+    fn segment_count(&self) -> usize {
+        self.points.len().min(self.tangents.len()).saturating_sub(1)
+    }
+
+    fn evaluate(&self, t: f32) -> T {
+        let count = self.segment_count();
+        if count == 0 {
+            return self.points.first().cloned().unwrap_or_else(T::zero);
+        }
+        let (i, u) = segment(t, count);
+        T::hermite(self.points[i], self.tangents[i], self.points[i + 1], self.tangents[i + 1], u)
+    }
+
+    fn derivative(&self, t: f32) -> T {
+        let count = self.segment_count();
+        if count == 0 {
+            return T::zero();
+        }
+        let (i, u) = segment(t, count);
+        T::hermite_derivative(self.points[i], self.tangents[i], self.points[i + 1], self.tangents[i + 1], u) * count as f32
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum CatmullRomParameterization {
+    Uniform,
+    Centripetal,
+    Chordal
+}
+
+impl CatmullRomParameterization {
+    fn alpha(&self) -> f32 {
+        match *self {
+            CatmullRomParameterization::Uniform     => 0f32,
+            CatmullRomParameterization::Centripetal => 0.5f32,
+            CatmullRomParameterization::Chordal     => 1f32
+        }
+    }
+}
+
+// passes through points[1] .. points[n - 2]; the first and last points are control points.
+#[derive(Clone)]
+pub struct CatmullRomSpline<T: CurvePoint> {
+    pub points:           Vec<T>,
+    pub parameterization: CatmullRomParameterization
+}
+
+impl<T: CurvePoint> CatmullRomSpline<T> {
+
+    pub fn new(points: Vec<T>, parameterization: CatmullRomParameterization) -> CatmullRomSpline<T> {
+        CatmullRomSpline {
+            points,
+            parameterization
+        }
+    }
+
+    fn hermite_segment(&self, i: usize) -> (T, T, T, T) {
+        let p0    = self.points[i];
+        let p1    = self.points[i + 1];
+        let p2    = self.points[i + 2];
+        let p3    = self.points[i + 3];
+        let alpha = self.parameterization.alpha();
+        let knot  = |a: T, b: T| (b - a).length().powf(alpha).max(1e-4f32);
+        let dt0   = knot(p0, p1);
+        let dt1   = knot(p1, p2);
+        let dt2   = knot(p2, p3);
+        let m1 = (((p1 - p0) * (1f32 / dt0)) - ((p2 - p0) * (1f32 / (dt0 + dt1)))) + ((p2 - p1) * (1f32 / dt1));
+        let m2 = (((p2 - p1) * (1f32 / dt1)) - ((p3 - p1) * (1f32 / (dt1 + dt2)))) + ((p3 - p2) * (1f32 / dt2));
+        (p1, m1 * dt1, p2, m2 * dt1)
+    }
+}
+
+impl<T: CurvePoint> Curve<T> for CatmullRomSpline<T> {
+
+    fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(3)
+    }
+
+    fn evaluate(&self, t: f32) -> T {
+        let count = self.segment_count();
+        if count == 0 {
+            return self.points.first().cloned().unwrap_or_else(T::zero);
+        }
+        let (i, u) = segment(t, count);
+        let (p1, m1, p2, m2) = self.hermite_segment(i);
+        T::hermite(p1, m1, p2, m2, u)
+    }
+
+    fn derivative(&self, t: f32) -> T {
+        let count = self.segment_count();
+        if count == 0 {
+            return T::zero();
+        }
+        let (i, u) = segment(t, count);
+        let (p1, m1, p2, m2) = self.hermite_segment(i);
+        T::hermite_derivative(p1, m1, p2, m2, u) * count as f32
+    }
+}
+
+// uniform cubic b-spline. approximates rather than interpolates its control points.
+#[derive(Clone)]
+pub struct BSpline<T: CurvePoint> {
+    pub points: Vec<T>
+}
+
+impl<T: CurvePoint> BSpline<T> {
+    pub fn new(points: Vec<T>) -> BSpline<T> {
+        BSpline {
+            points
+        }
+    }
+}
+
+impl<T: CurvePoint> Curve<T> for BSpline<T> {
+
+    fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(3)
+    }
+
+    fn evaluate(&self, t: f32) -> T {
+        let count = self.segment_count();
+        if count == 0 {
+            return self.points.first().cloned().unwrap_or_else(T::zero);
+        }
+        let (i, u) = segment(t, count);
+        let u2 = u * u;
+        let u3 = u2 * u;
+        let b0 = (1f32 - u) * (1f32 - u) * (1f32 - u);
+        let b1 = ((3f32 * u3) - (6f32 * u2)) + 4f32;
+        let b2 = (((-3f32 * u3) + (3f32 * u2)) + (3f32 * u)) + 1f32;
+        let b3 = u3;
+        ((((self.points[i] * b0) + (self.points[i + 1] * b1)) + (self.points[i + 2] * b2)) + (self.points[i + 3] * b3)) * (1f32 / 6f32)
+    }
+
+    fn derivative(&self, t: f32) -> T {
+        let count = self.segment_count();
+        if count == 0 {
+            return T::zero();
+        }
+        let (i, u) = segment(t, count);
+        let u2 = u * u;
+        let b0 = -3f32 * (1f32 - u) * (1f32 - u);
+        let b1 = (9f32 * u2) - (12f32 * u);
+        let b2 = ((-9f32 * u2) + (6f32 * u)) + 3f32;
+        let b3 = 3f32 * u2;
+        ((((self.points[i] * b0) + (self.points[i + 1] * b1)) + (self.points[i + 2] * b2)) + (self.points[i + 3] * b3)) * (count as f32 / 6f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector2, Vector3};
+    use super::{Curve, CurvePoint, ArcLengthTable, CubicBezier, HermiteSpline, CatmullRomSpline, CatmullRomParameterization, BSpline};
+
+    fn near<T: CurvePoint>(a: T, b: T, tolerance: f32) -> bool {
+        (a - b).length() <= tolerance
+    }
+
+    // central difference against the analytic derivative, away from segment
+    // joins where non uniform splines are only c0 in the global parameter.
+    fn check_derivative<T: CurvePoint, C: Curve<T>>(curve: &C) {
+        let h = 1e-3f32;
+        for i in 0..20 {
+            let t = (i as f32 + 0.5f32) / 20f32;
+            let numeric = (curve.evaluate(t + h) - curve.evaluate(t - h)) * (0.5f32 / h);
+            let analytic = curve.derivative(t);
+            assert!(near(numeric, analytic, 2e-2f32 * analytic.length().max(1f32)));
+        }
+    }
+
+    fn points() -> Vec<Vector3> {
+        vec![Vector3::new(0f32, 0f32, 0f32), Vector3::new(1f32, 2f32, 0f32), Vector3::new(3f32, 2f32, 1f32),
+             Vector3::new(4f32, 0f32, 2f32), Vector3::new(6f32, 1f32, 2f32)]
+    }
+
+    #[test]
+    fn bezier_ends_and_derivative() {
+        let curve = CubicBezier::new(Vector2::new(0f32, 0f32), Vector2::new(1f32, 2f32), Vector2::new(3f32, 2f32), Vector2::new(4f32, 0f32));
+        assert!(near(curve.evaluate(0f32), curve.p0, 1e-6f32));
+        assert!(near(curve.evaluate(1f32), curve.p3, 1e-6f32));
+        assert!(near(curve.derivative(0f32), (curve.p1 - curve.p0) * 3f32, 1e-5f32));
+        check_derivative(&curve);
+    }
+
+    #[test]
+    fn hermite_interpolates_points_and_tangents() {
+        let points   = points();
+        let tangents = vec![Vector3::new(1f32, 0f32, 0f32); points.len()];
+        let curve = HermiteSpline::new(points.clone(), tangents.clone());
+        let count = curve.segment_count() as f32;
+        for (i, point) in points.iter().enumerate() {
+            let t = i as f32 / count;
+            assert!(near(curve.evaluate(t), *point, 1e-5f32));
+            assert!(near(curve.derivative(t), tangents[i] * count, 1e-4f32));
+        }
+        check_derivative(&curve);
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_inner_points() {
+        let points = points();
+        for &parameterization in &[CatmullRomParameterization::Uniform, CatmullRomParameterization::Centripetal, CatmullRomParameterization::Chordal] {
+            let curve = CatmullRomSpline::new(points.clone(), parameterization);
+            assert_eq!(curve.segment_count(), 2);
+            assert!(near(curve.evaluate(0f32), points[1], 1e-5f32));
+            assert!(near(curve.evaluate(0.5f32), points[2], 1e-5f32));
+            assert!(near(curve.evaluate(1f32), points[3], 1e-5f32));
+            check_derivative(&curve);
+        }
+    }
+
+    #[test]
+    fn b_spline_derivative() {
+        check_derivative(&BSpline::new(points()));
+    }
+
+    #[test]
+    fn arc_length_of_a_straight_line() {
+        let curve = CubicBezier::new(Vector3::new(0f32, 0f32, 0f32), Vector3::new(1f32, 0f32, 0f32), Vector3::new(5f32, 0f32, 0f32), Vector3::new(6f32, 0f32, 0f32));
+        assert!((curve.arc_length() - 6f32).abs() < 1e-4f32);
+        let table = ArcLengthTable::new(&curve, 256);
+        for i in 0..7 {
+            let distance = i as f32;
+            assert!((curve.evaluate(table.parameter(distance)).x - distance).abs() < 1e-2f32);
+        }
+        assert_eq!(table.parameter(-1f32), 0f32);
+        assert_eq!(table.parameter(10f32), 1f32);
+    }
+
+    #[test]
+    fn closest_point_and_subdivide() {
+        let curve = CubicBezier::new(Vector2::new(0f32, 0f32), Vector2::new(1f32, 2f32), Vector2::new(3f32, 2f32), Vector2::new(4f32, 0f32));
+        let t = curve.closest_parameter(Vector2::new(2f32, 5f32));
+        assert!((t - 0.5f32).abs() < 1e-3f32);
+        assert!(near(curve.closest_point(Vector2::new(-1f32, -1f32)), curve.p0, 1e-4f32));
+        let points = curve.subdivide(1e-3f32);
+        assert!(points.len() > 8);
+        assert!(near(points[0], curve.p0, 1e-6f32));
+        assert!(near(points[points.len() - 1], curve.p3, 1e-6f32));
+    }
+}
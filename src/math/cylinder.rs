@@ -0,0 +1,246 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::matrix::Matrix;
+use super::plane::{Plane, IntersectionType};
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::query;
+use super::gjk;
+use super::scalar::EPSILON;
+
+// a capped cylinder around the segment from start to end.
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Cylinder {
+    pub start:  Vector3,
+    pub end:    Vector3,
+    pub radius: f32
+}
+
+impl Cylinder {
+
+    pub fn new(start: Vector3, end: Vector3, radius: f32) -> Cylinder {
+        Cylinder {
+            start,
+            end,
+            radius
+        }
+    }
+
+    // zero for a cylinder with no height, which then behaves as a sphere of
+    // the radius about start, as a capsule would.
+    pub fn axis(&self) -> Vector3 {
+        let axis   = self.end - self.start;
+        let length = axis.length();
+        if length <= EPSILON { Vector3::zero() } else { axis / length }
+    }
+
+    pub fn to_bounding_box(self) -> BoundingBox {
+        let axis   = self.axis();
+        let extent = Vector3::new(
+            self.radius * (1f32 - (axis.x * axis.x)).max(0f32).sqrt(),
+            self.radius * (1f32 - (axis.y * axis.y)).max(0f32).sqrt(),
+            self.radius * (1f32 - (axis.z * axis.z)).max(0f32).sqrt());
+        BoundingBox::new(Vector3::min(self.start, self.end) - extent, Vector3::max(self.start, self.end) + extent)
+    }
+
+    pub fn to_bounding_sphere(self) -> BoundingSphere {
+        let half = Vector3::distance(self.start, self.end) * 0.5f32;
+        BoundingSphere::new((self.start + self.end) * 0.5f32, ((half * half) + (self.radius * self.radius)).sqrt())
+    }
+
+    // the radius scales by the largest axis scale of the matrix, so non
+    // uniform scaling gives the cylinder enclosing the scaled shape.
+    pub fn transform(cylinder: Cylinder, matrix: Matrix) -> Cylinder {
+        let scale = matrix.right().length().max(matrix.up().length()).max(matrix.backward().length());
+        Cylinder {
+            start:  Vector3::transform(cylinder.start, matrix),
+            end:    Vector3::transform(cylinder.end, matrix),
+            radius: cylinder.radius * scale
+        }
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        let axis   = self.end - self.start;
+        let length = Vector3::dot(axis, axis);
+        let y      = Vector3::dot(point - self.start, axis);
+        if y < 0f32 || y > length {
+            return false;
+        }
+        let radial = (point - self.start) - (axis * (y / length.max(EPSILON)));
+        radial.length_squared() <= self.radius * self.radius
+    }
+
+    // distance along the ray in units of direction, 0 when the ray starts inside.
+    pub fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        if self.contains_point(position) {
+            return Some(0f32);
+        }
+        let axis = self.end - self.start;
+        let baba = Vector3::dot(axis, axis);
+        if baba <= EPSILON * EPSILON {
+            return BoundingSphere::new(self.start, self.radius).intersects_ray(position, direction);
+        }
+        let bard = Vector3::dot(axis, direction);
+        let baoa = Vector3::dot(axis, position - self.start);
+        let mut best = query::ray_cylinder_side(position, direction, self.start, self.end, self.radius);
+        if bard.abs() > EPSILON {
+            for (cap, offset) in [(self.start, 0f32), (self.end, baba)] {
+                let t = (offset - baoa) / bard;
+                if t < 0f32 || best.is_some_and(|best| best <= t) {
+                    continue;
+                }
+                let point = (position + (direction * t)) - cap;
+                if point.length_squared() <= self.radius * self.radius {
+                    best = Some(t);
+                }
+            }
+        }
+        best
+    }
+
+    pub fn intersects_sphere(&self, sphere: BoundingSphere) -> bool {
+        gjk::intersects(self, &sphere)
+    }
+
+    pub fn intersects_box(&self, bounds: BoundingBox) -> bool {
+        gjk::intersects(self, &bounds)
+    }
+
+    pub fn intersects_plane(&self, plane: Plane) -> IntersectionType {
+        let plane  = Plane::normalize(plane);
+        let normal = Vector3::new(plane.a, plane.b, plane.c);
+        let cosine = Vector3::dot(normal, self.axis());
+        let reach  = self.radius * (1f32 - (cosine * cosine)).max(0f32).sqrt();
+        let start  = Plane::dot_coordinate(plane, self.start);
+        let end    = Plane::dot_coordinate(plane, self.end);
+        if start.min(end) > reach {
+            IntersectionType::front
+        } else if start.max(end) < -reach {
+            IntersectionType::back
+        } else {
+            IntersectionType::intersecting
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Matrix, Plane, IntersectionType, BoundingBox, BoundingSphere};
+    use super::Cylinder;
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-4f32
+    }
+
+    fn upright() -> Cylinder {
+        Cylinder::new(Vector3::zero(), Vector3::new(0f32, 4f32, 0f32), 1f32)
+    }
+
+    #[test]
+    fn contains_point_stops_at_the_caps() {
+        let cylinder = upright();
+        assert!(cylinder.contains_point(Vector3::new(0.9f32, 4f32, 0f32)));
+        assert!(cylinder.contains_point(Vector3::new(0f32, 0f32, -1f32)));
+        assert!(!cylinder.contains_point(Vector3::new(0f32, 4.1f32, 0f32)));
+        assert!(!cylinder.contains_point(Vector3::new(1.1f32, 2f32, 0f32)));
+    }
+
+    #[test]
+    fn ray_hits_side_and_caps() {
+        let cylinder = upright();
+        let side = cylinder.intersects_ray(Vector3::new(-5f32, 2f32, 0f32), Vector3::UNIT_X).unwrap();
+        assert!((side - 4f32).abs() < 1e-4f32);
+        let cap = cylinder.intersects_ray(Vector3::new(0.5f32, 10f32, 0f32), -Vector3::UNIT_Y).unwrap();
+        assert!((cap - 6f32).abs() < 1e-4f32);
+        let bottom = cylinder.intersects_ray(Vector3::new(0f32, -3f32, 0.5f32), Vector3::UNIT_Y * 2f32).unwrap();
+        assert!((bottom - 1.5f32).abs() < 1e-4f32);
+        assert_eq!(cylinder.intersects_ray(Vector3::new(0f32, 2f32, 0f32), Vector3::UNIT_X), Some(0f32));
+        assert!(cylinder.intersects_ray(Vector3::new(1.5f32, 10f32, 0f32), -Vector3::UNIT_Y).is_none());
+        assert!(cylinder.intersects_ray(Vector3::new(-5f32, 4.5f32, 0f32), Vector3::UNIT_X).is_none());
+    }
+
+    #[test]
+    fn intersects_other_shapes() {
+        let cylinder = upright();
+        assert!(cylinder.intersects_sphere(BoundingSphere::new(Vector3::new(0f32, 5f32, 0f32), 1.1f32)));
+        assert!(!cylinder.intersects_sphere(BoundingSphere::new(Vector3::new(0f32, 5f32, 0f32), 0.9f32)));
+        assert!(!cylinder.intersects_sphere(BoundingSphere::new(Vector3::new(1.5f32, 4.5f32, 0f32), 0.6f32)));
+        assert!(cylinder.intersects_sphere(BoundingSphere::new(Vector3::new(1.5f32, 4.5f32, 0f32), 0.8f32)));
+        assert!(cylinder.intersects_box(BoundingBox::new(Vector3::new(0.5f32, 3f32, -0.2f32), Vector3::new(2f32, 5f32, 0.2f32))));
+        assert!(!cylinder.intersects_box(BoundingBox::new(Vector3::new(0.5f32, 4.2f32, -0.2f32), Vector3::new(2f32, 5f32, 0.2f32))));
+    }
+
+    #[test]
+    fn classifies_against_planes() {
+        let cylinder = upright();
+        assert!(cylinder.intersects_plane(Plane::new(0f32, 1f32, 0f32, 0.1f32)) == IntersectionType::front);
+        assert!(cylinder.intersects_plane(Plane::new(0f32, 1f32, 0f32, -4.5f32)) == IntersectionType::back);
+        assert!(cylinder.intersects_plane(Plane::new(1f32, 0f32, 0f32, -0.5f32)) == IntersectionType::intersecting);
+        assert!(cylinder.intersects_plane(Plane::new(1f32, 0f32, 0f32, -1.5f32)) == IntersectionType::back);
+    }
+
+    #[test]
+    fn bounds_and_transform() {
+        let cylinder = upright();
+        let bounds = cylinder.to_bounding_box();
+        assert!(near(bounds.min, Vector3::new(-1f32, 0f32, -1f32)));
+        assert!(near(bounds.max, Vector3::new(1f32, 4f32, 1f32)));
+        let sphere = cylinder.to_bounding_sphere();
+        assert!(near(sphere.center, Vector3::new(0f32, 2f32, 0f32)));
+        assert!((sphere.radius - 5f32.sqrt()).abs() < 1e-4f32);
+        let tilted = Cylinder::new(Vector3::zero(), Vector3::new(4f32, 4f32, 0f32), 1f32).to_bounding_box();
+        let reach = 0.5f32.sqrt();
+        assert!(near(tilted.min, Vector3::new(-reach, -reach, -1f32)));
+        assert!(near(tilted.max, Vector3::new(4f32 + reach, 4f32 + reach, 1f32)));
+        let matrix = Matrix::mul(Matrix::scale(Vector3::new(1f32, 1f32, 3f32)), Matrix::translation(Vector3::UNIT_Z));
+        let moved = Cylinder::transform(cylinder, matrix);
+        assert!(near(moved.start, Vector3::new(0f32, 0f32, 1f32)));
+        assert!(near(moved.end, Vector3::new(0f32, 4f32, 1f32)));
+        assert!((moved.radius - 3f32).abs() < 1e-4f32);
+    }
+
+    #[test]
+    fn zero_height_behaves_as_a_sphere() {
+        let center   = Vector3::new(1f32, 2f32, 3f32);
+        let cylinder = Cylinder::new(center, center, 1f32);
+        assert!(cylinder.axis().equals(Vector3::zero()));
+        let bounds = cylinder.to_bounding_box();
+        assert!(near(bounds.min, center - Vector3::ONE) && near(bounds.max, center + Vector3::ONE));
+        assert!(cylinder.intersects_plane(Plane::new(0f32, 1f32, 0f32, -2.5f32)) == IntersectionType::intersecting);
+        assert!(cylinder.intersects_plane(Plane::new(0f32, 1f32, 0f32, -3.5f32)) == IntersectionType::back);
+        assert!(cylinder.intersects_plane(Plane::new(0f32, 1f32, 0f32, 0f32)) == IntersectionType::front);
+        let t = cylinder.intersects_ray(Vector3::new(-4f32, 2f32, 3f32), Vector3::UNIT_X).unwrap();
+        assert!((t - 4f32).abs() < 1e-4f32);
+        assert!(cylinder.contains_point(center + Vector3::new(0f32, 0.5f32, 0f32)));
+        assert!(!cylinder.contains_point(center + Vector3::new(0f32, 1.5f32, 0f32)));
+    }
+}
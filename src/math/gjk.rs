@@ -0,0 +1,554 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::quaternion::Quaternion;
+use super::matrix::Matrix;
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::oriented_bounding_box::OrientedBoundingBox;
+use super::capsule::Capsule;
+use super::cylinder::Cylinder;
+use super::triangle::Triangle;
+use super::convex_hull::ConvexHull;
+
+// gjk and epa over any pair of convex shapes described by a support function.
+// everything works on the minkowski difference a - b, whose point closest to
+// the origin gives the separation, or when the origin is inside, the
+// penetration.
+
+const MAX_ITERATIONS:     usize = 64;
+const MAX_EPA_ITERATIONS: usize = 256;
+const TOLERANCE:          f32   = 1e-6f32;
+
+pub trait Support {
+    // the point of the shape furthest along direction. direction need not be
+    // unit length.
+    fn support(&self, direction: Vector3) -> Vector3;
+}
+
+impl<T: Support> Support for &T {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        (*self).support(direction)
+    }
+}
+
+impl Support for Vector3 {
+    fn support(&self, _: Vector3) -> Vector3 {
+        *self
+    }
+}
+
+impl Support for BoundingSphere {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        self.center + (unit_or_x(direction) * self.radius)
+    }
+}
+
+impl Support for BoundingBox {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        Vector3::new(
+            if direction.x >= 0f32 { self.max.x } else { self.min.x },
+            if direction.y >= 0f32 { self.max.y } else { self.min.y },
+            if direction.z >= 0f32 { self.max.z } else { self.min.z })
+    }
+}
+
+impl Support for OrientedBoundingBox {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let extents = self.extents();
+        self.axes.iter().zip(extents.iter()).fold(self.center, |point, (axis, extent)| {
+            if Vector3::dot(direction, *axis) >= 0f32 { point + (*axis * *extent) } else { point - (*axis * *extent) }
+        })
+    }
+}
+
+impl Support for Capsule {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let end = if Vector3::dot(self.end - self.start, direction) >= 0f32 { self.end } else { self.start };
+        end + (unit_or_x(direction) * self.radius)
+    }
+}
+
+impl Support for Cylinder {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let axis   = self.end - self.start;
+        let end    = if Vector3::dot(axis, direction) >= 0f32 { self.end } else { self.start };
+        let radial = Vector3::reject(direction, axis);
+        if radial.length_squared() <= TOLERANCE * TOLERANCE {
+            return end;
+        }
+        end + (radial.normalize() * self.radius)
+    }
+}
+
+impl Support for Triangle {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        [self.b, self.c].iter().fold(self.a, |best, vertex| {
+            if Vector3::dot(*vertex, direction) > Vector3::dot(best, direction) { *vertex } else { best }
+        })
+    }
+}
+
+impl Support for ConvexHull {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        ConvexHull::support(self, direction)
+    }
+}
+
+// a + b, e.g. a box swept by a sphere for a rounded box.
+pub struct MinkowskiSum<A: Support, B: Support> {
+    pub a: A,
+    pub b: B
+}
+
+impl<A: Support, B: Support> MinkowskiSum<A, B> {
+    pub fn new(a: A, b: B) -> MinkowskiSum<A, B> {
+        MinkowskiSum { a, b }
+    }
+}
+
+impl<A: Support, B: Support> Support for MinkowskiSum<A, B> {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        self.a.support(direction) + self.b.support(direction)
+    }
+}
+
+// a shape placed by an affine matrix. directions are taken into local space
+// with the transposed linear part, so no inverse is needed.
+pub struct Transformed<S: Support> {
+    pub shape:  S,
+    pub matrix: Matrix
+}
+
+impl<S: Support> Transformed<S> {
+
+    pub fn new(shape: S, matrix: Matrix) -> Transformed<S> {
+        Transformed { shape, matrix }
+    }
+
+    pub fn from_trs(shape: S, translation: Vector3, rotation: Quaternion, scale: Vector3) -> Transformed<S> {
+        let matrix = (Matrix::scale(scale) * Matrix::from_quaternion(rotation)) * Matrix::translation(translation);
+        Transformed { shape, matrix }
+    }
+}
+
+impl<S: Support> Support for Transformed<S> {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let local = Vector3::transform_normal(direction, Matrix::transpose(self.matrix));
+        Vector3::transform(self.shape.support(local), self.matrix)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ClosestPoints {
+    pub point_a:  Vector3,
+    pub point_b:  Vector3,
+    pub distance: f32
+}
+
+// normal points from a towards b: moving b by normal * depth separates the
+// shapes. the points are the deepest points of each shape inside the other.
+#[derive(Copy, Clone)]
+pub struct Contact {
+    pub normal:  Vector3,
+    pub depth:   f32,
+    pub point_a: Vector3,
+    pub point_b: Vector3
+}
+
+pub fn intersects<A: Support, B: Support>(a: &A, b: &B) -> bool {
+    match gjk(a, b, true) {
+        Gjk::Intersecting(_) => true,
+        Gjk::Separated(_)    => false
+    }
+}
+
+// none when the shapes intersect.
+pub fn closest_points<A: Support, B: Support>(a: &A, b: &B) -> Option<ClosestPoints> {
+    match gjk(a, b, false) {
+        Gjk::Intersecting(_)  => None,
+        Gjk::Separated(points) => Some(points)
+    }
+}
+
+pub fn distance<A: Support, B: Support>(a: &A, b: &B) -> f32 {
+    closest_points(a, b).map_or(0f32, |points| points.distance)
+}
+
+// none when the shapes do not intersect.
+pub fn penetration<A: Support, B: Support>(a: &A, b: &B) -> Option<Contact> {
+    match gjk(a, b, false) {
+        Gjk::Intersecting(simplex) => Some(epa(a, b, simplex)),
+        Gjk::Separated(_)          => None
+    }
+}
+
+// a point of the minkowski difference with the shape points it came from.
+#[derive(Copy, Clone)]
+struct Vertex {
+    point: Vector3,
+    a:     Vector3,
+    b:     Vector3
+}
+
+fn support<A: Support, B: Support>(a: &A, b: &B, direction: Vector3) -> Vertex {
+    let pa = a.support(direction);
+    let pb = b.support(-direction);
+    Vertex { point: pa - pb, a: pa, b: pb }
+}
+
+enum Gjk {
+    Intersecting(Vec<Vertex>),
+    Separated(ClosestPoints)
+}
+
+fn gjk<A: Support, B: Support>(a: &A, b: &B, boolean: bool) -> Gjk {
+    let mut simplex = vec![support(a, b, Vector3::UNIT_X)];
+    let mut weights = vec![1f32];
+    let mut v = simplex[0].point;
+    for _ in 0..MAX_ITERATIONS {
+        let v_squared = Vector3::dot(v, v);
+        if v_squared <= TOLERANCE * TOLERANCE {
+            return Gjk::Intersecting(simplex);
+        }
+        let w = support(a, b, -v);
+        let dot = Vector3::dot(v, w.point);
+        if boolean && dot > 0f32 {
+            break;
+        }
+        // no progress towards the origin: v is the closest point.
+        if v_squared - dot <= TOLERANCE * v_squared ||
+           simplex.iter().any(|s| (s.point - w.point).length_squared() <= TOLERANCE * TOLERANCE) {
+            break;
+        }
+        simplex.push(w);
+        let (closest, reduced, reduced_weights) = closest_on_simplex(&simplex);
+        if reduced.len() == 4 {
+            return Gjk::Intersecting(reduced);
+        }
+        if Vector3::dot(closest, closest) >= v_squared {
+            break;
+        }
+        v       = closest;
+        simplex = reduced;
+        weights = reduced_weights;
+    }
+    if boolean && Vector3::dot(v, v) <= TOLERANCE * TOLERANCE {
+        return Gjk::Intersecting(simplex);
+    }
+    let point_a = simplex.iter().zip(&weights).fold(Vector3::zero(), |p, (s, w)| p + (s.a * *w));
+    let point_b = simplex.iter().zip(&weights).fold(Vector3::zero(), |p, (s, w)| p + (s.b * *w));
+    Gjk::Separated(ClosestPoints { point_a, point_b, distance: v.length() })
+}
+
+// closest point to the origin on the simplex, the smallest sub simplex that
+// still contains it and the barycentric weights over that sub simplex. a
+// tetrahedron is returned whole when it contains the origin.
+fn closest_on_simplex(simplex: &[Vertex]) -> (Vector3, Vec<Vertex>, Vec<f32>) {
+    match simplex.len() {
+        1 => (simplex[0].point, simplex.to_vec(), vec![1f32]),
+        2 => closest_on_segment(simplex[0], simplex[1]),
+        3 => closest_on_triangle(simplex[0], simplex[1], simplex[2]),
+        _ => closest_on_tetrahedron(simplex)
+    }
+}
+
+fn closest_on_segment(a: Vertex, b: Vertex) -> (Vector3, Vec<Vertex>, Vec<f32>) {
+    let ab = b.point - a.point;
+    let t  = Vector3::dot(-a.point, ab) / Vector3::dot(ab, ab).max(f32::MIN_POSITIVE);
+    if t <= 0f32 {
+        return (a.point, vec![a], vec![1f32]);
+    }
+    if t >= 1f32 {
+        return (b.point, vec![b], vec![1f32]);
+    }
+    (a.point + (ab * t), vec![a, b], vec![1f32 - t, t])
+}
+
+// ericson's voronoi region test, with the origin as the query point.
+fn closest_on_triangle(a: Vertex, b: Vertex, c: Vertex) -> (Vector3, Vec<Vertex>, Vec<f32>) {
+    let ab = b.point - a.point;
+    let ac = c.point - a.point;
+    let d1 = Vector3::dot(ab, -a.point);
+    let d2 = Vector3::dot(ac, -a.point);
+    if d1 <= 0f32 && d2 <= 0f32 {
+        return (a.point, vec![a], vec![1f32]);
+    }
+    let d3 = Vector3::dot(ab, -b.point);
+    let d4 = Vector3::dot(ac, -b.point);
+    if d3 >= 0f32 && d4 <= d3 {
+        return (b.point, vec![b], vec![1f32]);
+    }
+    let vc = (d1 * d4) - (d3 * d2);
+    if vc <= 0f32 && d1 >= 0f32 && d3 <= 0f32 {
+        let v = d1 / (d1 - d3);
+        return (a.point + (ab * v), vec![a, b], vec![1f32 - v, v]);
+    }
+    let d5 = Vector3::dot(ab, -c.point);
+    let d6 = Vector3::dot(ac, -c.point);
+    if d6 >= 0f32 && d5 <= d6 {
+        return (c.point, vec![c], vec![1f32]);
+    }
+    let vb = (d5 * d2) - (d1 * d6);
+    if vb <= 0f32 && d2 >= 0f32 && d6 <= 0f32 {
+        let w = d2 / (d2 - d6);
+        return (a.point + (ac * w), vec![a, c], vec![1f32 - w, w]);
+    }
+    let va = (d3 * d6) - (d5 * d4);
+    if va <= 0f32 && (d4 - d3) >= 0f32 && (d5 - d6) >= 0f32 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b.point + ((c.point - b.point) * w), vec![b, c], vec![1f32 - w, w]);
+    }
+    let denom = 1f32 / ((va + vb) + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    (a.point + (ab * v) + (ac * w), vec![a, b, c], vec![(1f32 - v) - w, v, w])
+}
+
+fn closest_on_tetrahedron(simplex: &[Vertex]) -> (Vector3, Vec<Vertex>, Vec<f32>) {
+    let faces = [(0, 1, 2, 3), (0, 2, 3, 1), (0, 3, 1, 2), (1, 3, 2, 0)];
+    let mut best: Option<(Vector3, Vec<Vertex>, Vec<f32>)> = None;
+    for &(i, j, k, l) in &faces {
+        let (a, b, c, d) = (simplex[i].point, simplex[j].point, simplex[k].point, simplex[l].point);
+        let normal   = Vector3::cross(b - a, c - a);
+        let origin   = Vector3::dot(normal, -a);
+        let opposite = Vector3::dot(normal, d - a);
+        // origin on the far side of this face from the fourth vertex. flat
+        // tetrahedra test every face.
+        if origin * opposite < 0f32 || opposite.abs() <= TOLERANCE * TOLERANCE {
+            let candidate = closest_on_triangle(simplex[i], simplex[j], simplex[k]);
+            let closer = match best {
+                Some(ref b) => Vector3::dot(candidate.0, candidate.0) < Vector3::dot(b.0, b.0),
+                None        => true
+            };
+            if closer {
+                best = Some(candidate);
+            }
+        }
+    }
+    best.unwrap_or_else(|| (Vector3::zero(), simplex.to_vec(), vec![0.25f32; 4]))
+}
+
+#[derive(Copy, Clone)]
+struct Face {
+    vertices: [usize; 3],
+    normal:   Vector3,
+    distance: f32
+}
+
+// none for degenerate faces, whose normal is undefined.
+fn face(polytope: &[Vertex], vertices: [usize; 3]) -> Option<Face> {
+    let (a, b, c) = (polytope[vertices[0]].point, polytope[vertices[1]].point, polytope[vertices[2]].point);
+    let cross  = Vector3::cross(b - a, c - a);
+    let length = cross.length();
+    if length <= TOLERANCE || !length.is_finite() {
+        return None;
+    }
+    let normal = cross / length;
+    Some(Face { vertices, normal, distance: Vector3::dot(normal, a) })
+}
+
+fn closest_face(faces: &[Face]) -> Option<usize> {
+    (0..faces.len()).min_by(|&i, &j| faces[i].distance.total_cmp(&faces[j].distance))
+}
+
+// grows the gjk simplex into a tetrahedron around the origin.
+fn blow_up<A: Support, B: Support>(a: &A, b: &B, mut simplex: Vec<Vertex>) -> Vec<Vertex> {
+    let axes = [Vector3::UNIT_X, Vector3::UNIT_Y, Vector3::UNIT_Z];
+    if simplex.len() == 1 {
+        for direction in axes.iter().flat_map(|axis| vec![*axis, -*axis]) {
+            let w = support(a, b, direction);
+            if (w.point - simplex[0].point).length_squared() > TOLERANCE {
+                simplex.push(w);
+                break;
+            }
+        }
+    }
+    if simplex.len() == 2 {
+        let line = simplex[1].point - simplex[0].point;
+        let mut direction = Vector3::any_orthogonal(line);
+        let rotation = Matrix::rotation_axis(line.normalize(), std::f32::consts::PI / 3f32);
+        for _ in 0..6 {
+            let w = support(a, b, direction);
+            if Vector3::cross(w.point - simplex[0].point, line).length_squared() > TOLERANCE {
+                simplex.push(w);
+                break;
+            }
+            direction = Vector3::transform_normal(direction, rotation);
+        }
+    }
+    if simplex.len() == 3 {
+        let normal = Vector3::cross(simplex[1].point - simplex[0].point, simplex[2].point - simplex[0].point);
+        let w = support(a, b, normal);
+        let w = if Vector3::dot(w.point - simplex[0].point, normal).abs() > TOLERANCE { w } else { support(a, b, -normal) };
+        simplex.push(w);
+    }
+    simplex
+}
+
+fn epa<A: Support, B: Support>(a: &A, b: &B, simplex: Vec<Vertex>) -> Contact {
+    let mut polytope = blow_up(a, b, simplex);
+    if polytope.len() < 4 {
+        // flat difference, touching at best.
+        return Contact { normal: Vector3::UNIT_X, depth: 0f32, point_a: polytope[0].a, point_b: polytope[0].b };
+    }
+    let centroid = (((polytope[0].point + polytope[1].point) + polytope[2].point) + polytope[3].point) * 0.25f32;
+    let mut faces: Vec<Face> = Vec::new();
+    for vertices in &[[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]] {
+        let f = match face(&polytope, *vertices) {
+            Some(f) => f,
+            None    => continue
+        };
+        if Vector3::dot(f.normal, polytope[vertices[0]].point - centroid) < 0f32 {
+            faces.extend(face(&polytope, [vertices[0], vertices[2], vertices[1]]));
+        } else {
+            faces.push(f);
+        }
+    }
+    // the closest face distance only grows on a convex polytope, a drop means
+    // rounding has folded it and the last closest face is kept.
+    let mut best: Option<Face> = None;
+    for _ in 0..MAX_EPA_ITERATIONS {
+        let closest = match closest_face(&faces) {
+            Some(closest) => closest,
+            None          => break
+        };
+        if best.is_some_and(|b| faces[closest].distance < b.distance - TOLERANCE) {
+            break;
+        }
+        best = Some(faces[closest]);
+        let normal = faces[closest].normal;
+        let w = support(a, b, normal);
+        if Vector3::dot(w.point, normal) - faces[closest].distance <= TOLERANCE.max(faces[closest].distance * 1e-4f32) {
+            break;
+        }
+        let index = polytope.len();
+        polytope.push(w);
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        faces.retain(|f| {
+            // faces w is coplanar with stay, otherwise a w in line with one of
+            // their edges leaves a degenerate face and a hole in the polytope.
+            if Vector3::dot(f.normal, w.point - polytope[f.vertices[0]].point) <= TOLERANCE {
+                return true;
+            }
+            let v = f.vertices;
+            for edge in [(v[0], v[1]), (v[1], v[2]), (v[2], v[0])] {
+                // an edge shared by two removed faces is interior.
+                match horizon.iter().position(|e| *e == (edge.1, edge.0)) {
+                    Some(position) => { horizon.swap_remove(position); },
+                    None           => horizon.push(edge)
+                }
+            }
+            false
+        });
+        for (i, j) in horizon {
+            faces.extend(face(&polytope, [i, j, index]));
+        }
+    }
+    let closest = closest_face(&faces).map(|closest| faces[closest])
+                                      .filter(|f| best.is_none_or(|b| f.distance >= b.distance - TOLERANCE));
+    let f = match closest.or(best) {
+        Some(f) => f,
+        None    => return Contact { normal: Vector3::UNIT_X, depth: 0f32, point_a: polytope[0].a, point_b: polytope[0].b }
+    };
+    let (p0, p1, p2) = (polytope[f.vertices[0]], polytope[f.vertices[1]], polytope[f.vertices[2]]);
+    let (u, v, w) = Triangle::new(p0.point, p1.point, p2.point).barycentric_coordinates(f.normal * f.distance);
+    Contact {
+        normal:  f.normal,
+        depth:   f.distance,
+        point_a: ((p0.a * u) + (p1.a * v)) + (p2.a * w),
+        point_b: ((p0.b * u) + (p1.b * v)) + (p2.b * w)
+    }
+}
+
+fn unit_or_x(direction: Vector3) -> Vector3 {
+    let length = direction.length();
+    if length <= TOLERANCE { Vector3::UNIT_X } else { direction / length }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, BoundingBox, BoundingSphere};
+    use super::{intersects, distance, penetration};
+
+    fn overlap(a: BoundingBox, b: BoundingBox) -> f32 {
+        let x = (a.max.x - b.min.x).min(b.max.x - a.min.x);
+        let y = (a.max.y - b.min.y).min(b.max.y - a.min.y);
+        let z = (a.max.z - b.min.z).min(b.max.z - a.min.z);
+        x.min(y).min(z)
+    }
+
+    #[test]
+    fn sphere_sphere_distance() {
+        let a = BoundingSphere::new(Vector3::new(0f32, 0f32, 0f32), 1f32);
+        let b = BoundingSphere::new(Vector3::new(3f32, 4f32, 0f32), 2f32);
+        assert!(!intersects(&a, &b));
+        assert!((distance(&a, &b) - 2f32).abs() < 1e-3f32);
+    }
+
+    #[test]
+    fn sphere_sphere_depth() {
+        for &(offset, radius) in &[(0.5f32, 1f32), (1.5f32, 1f32), (1.2f32, 0.5f32), (0.2f32, 0.3f32)] {
+            let a = BoundingSphere::new(Vector3::new(1f32, 2f32, 3f32), 1f32);
+            let b = BoundingSphere::new(Vector3::new(1f32, 2f32, 3f32) + Vector3::new(2f32, 1f32, 2f32).normalize() * offset, radius);
+            let contact = penetration(&a, &b).unwrap();
+            let expected = (1f32 + radius) - offset;
+            assert!((contact.depth - expected).abs() < 2e-3f32, "{} {}", contact.depth, expected);
+            assert!(Vector3::dot(contact.normal, b.center - a.center) > 0f32);
+        }
+    }
+
+    #[test]
+    fn box_box_depth() {
+        let a = BoundingBox::new(Vector3::new(1.1511564f32, 0.18961307f32, -0.60741293f32), Vector3::new(2.061849f32, 0.89872575f32, 0.9450637f32));
+        let b = BoundingBox::new(Vector3::new(0.89897674f32, 0.0698688f32, -0.8076228f32), Vector3::new(2.5808728f32, 1.8418231f32, 1.0549089f32));
+        let contact = penetration(&a, &b).unwrap();
+        assert!((contact.depth - 0.829f32).abs() < 1e-3f32, "{}", contact.depth);
+
+        let a = BoundingBox::new(Vector3::new(-1f32, -1f32, -1f32), Vector3::new(1f32, 1f32, 1f32));
+        for i in 0..125 {
+            let offset = Vector3::new((i % 5) as f32, ((i / 5) % 5) as f32, (i / 25) as f32) * 0.35f32 - Vector3::new(0.7f32, 0.7f32, 0.7f32);
+            let b = BoundingBox::new(offset - Vector3::new(0.5f32, 0.75f32, 1f32), offset + Vector3::new(0.5f32, 0.75f32, 1f32));
+            let contact = penetration(&a, &b).unwrap();
+            assert!(contact.depth.is_finite());
+            assert!((contact.depth - overlap(a, b)).abs() < 1e-3f32, "{} {}", contact.depth, overlap(a, b));
+            let moved = contact.normal * (contact.depth + 1e-3f32);
+            assert!(!BoundingBox::new(b.min + moved, b.max + moved).intersects_box(a));
+        }
+    }
+
+    #[test]
+    fn separated_boxes() {
+        let a = BoundingBox::new(Vector3::new(0f32, 0f32, 0f32), Vector3::new(1f32, 1f32, 1f32));
+        let b = BoundingBox::new(Vector3::new(2f32, 0.5f32, 0.5f32), Vector3::new(3f32, 2f32, 2f32));
+        assert!(!intersects(&a, &b));
+        assert!(penetration(&a, &b).is_none());
+        assert!((distance(&a, &b) - 1f32).abs() < 1e-4f32);
+    }
+}
@@ -0,0 +1,201 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::fmt::{Display, Formatter, Error};
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::bool_vector::{BVector2, BVector3};
+
+// integer vectors for grids, voxels and tilemaps. IVector is signed (i32),
+// UVector unsigned (u32). conversions from float vectors state their rounding.
+
+macro_rules! integer_vector {
+    ($t:ident, $s:ty, $float:ident, $mask:ident, $($c:ident),+) => {
+        #[repr(C)]
+        #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $t {
+            $(pub $c: $s),+
+        }
+
+        impl Add<$t> for $t {
+            type Output = $t;
+            fn add(self, rhs: $t) -> $t { $t { $($c: self.$c + rhs.$c),+ } }
+        }
+
+        impl Sub<$t> for $t {
+            type Output = $t;
+            fn sub(self, rhs: $t) -> $t { $t { $($c: self.$c - rhs.$c),+ } }
+        }
+
+        impl Mul<$t> for $t {
+            type Output = $t;
+            fn mul(self, rhs: $t) -> $t { $t { $($c: self.$c * rhs.$c),+ } }
+        }
+
+        impl Mul<$s> for $t {
+            type Output = $t;
+            fn mul(self, rhs: $s) -> $t { $t { $($c: self.$c * rhs),+ } }
+        }
+
+        impl Div<$t> for $t {
+            type Output = $t;
+            fn div(self, rhs: $t) -> $t { $t { $($c: self.$c / rhs.$c),+ } }
+        }
+
+        impl Div<$s> for $t {
+            type Output = $t;
+            fn div(self, rhs: $s) -> $t { $t { $($c: self.$c / rhs),+ } }
+        }
+
+        impl_assign_op!($t, $t, AddAssign, add_assign, +);
+        impl_assign_op!($t, $t, SubAssign, sub_assign, -);
+        impl_assign_op!($t, $t, MulAssign, mul_assign, *);
+        impl_assign_op!($t, $s, MulAssign, mul_assign, *);
+        impl_assign_op!($t, $t, DivAssign, div_assign, /);
+        impl_assign_op!($t, $s, DivAssign, div_assign, /);
+        impl_ref_binop!($t, $t, Add, add);
+        impl_ref_binop!($t, $t, Sub, sub);
+        impl_ref_binop!($t, $t, Mul, mul);
+        impl_ref_binop!($t, $s, Mul, mul);
+        impl_ref_binop!($t, $t, Div, div);
+        impl_ref_binop!($t, $s, Div, div);
+        impl_comparisons!($t, $mask, $($c),+);
+
+        impl From<$t> for $float {
+            fn from(value: $t) -> $float {
+                $float::new($(value.$c as f32),+)
+            }
+        }
+
+        impl $t {
+
+            pub const ZERO: $t = $t { $($c: 0),+ };
+            pub const ONE:  $t = $t { $($c: 1),+ };
+
+            pub const fn new($($c: $s),+) -> $t {
+                $t { $($c),+ }
+            }
+
+            pub const fn splat(value: $s) -> $t {
+                $t { $($c: value),+ }
+            }
+
+            pub fn floor(value: $float) -> $t {
+                $t { $($c: value.$c.floor() as $s),+ }
+            }
+
+            pub fn round(value: $float) -> $t {
+                $t { $($c: value.$c.round() as $s),+ }
+            }
+
+            pub fn truncate(value: $float) -> $t {
+                $t { $($c: value.$c as $s),+ }
+            }
+
+            pub fn to_vector(self) -> $float {
+                $float::from(self)
+            }
+
+            pub fn min(value1: $t, value2: $t) -> $t {
+                $t { $($c: value1.$c.min(value2.$c)),+ }
+            }
+
+            pub fn max(value1: $t, value2: $t) -> $t {
+                $t { $($c: value1.$c.max(value2.$c)),+ }
+            }
+
+            // u64 as the sum of per axis distances can exceed u32.
+            pub fn manhattan_distance(value1: $t, value2: $t) -> u64 {
+                0u64 $(+ u64::from(value1.$c.abs_diff(value2.$c)))+
+            }
+
+            pub fn chebyshev_distance(value1: $t, value2: $t) -> u32 {
+                0 $(.max(value1.$c.abs_diff(value2.$c)))+
+            }
+        }
+    }
+}
+
+integer_vector!(IVector2, i32, Vector2, BVector2, x, y);
+integer_vector!(IVector3, i32, Vector3, BVector3, x, y, z);
+integer_vector!(UVector2, u32, Vector2, BVector2, x, y);
+integer_vector!(UVector3, u32, Vector3, BVector3, x, y, z);
+
+impl Neg for IVector2 {
+    type Output = IVector2;
+    fn neg(self) -> IVector2 {
+        IVector2::new(-self.x, -self.y)
+    }
+}
+
+impl Neg for IVector3 {
+    type Output = IVector3;
+    fn neg(self) -> IVector3 {
+        IVector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Display for IVector2 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for IVector3 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl Display for UVector2 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for UVector3 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{IVector2, IVector3, UVector3};
+
+    #[test]
+    fn distances_span_the_full_range() {
+        let low  = IVector3::splat(i32::MIN);
+        let high = IVector3::splat(i32::MAX);
+        assert_eq!(IVector3::manhattan_distance(low, high), 3 * u64::from(u32::MAX));
+        assert_eq!(IVector3::chebyshev_distance(low, high), u32::MAX);
+        assert_eq!(IVector2::manhattan_distance(IVector2::new(1, -2), IVector2::new(-3, 5)), 11);
+        assert_eq!(UVector3::manhattan_distance(UVector3::ZERO, UVector3::splat(u32::MAX)), 3 * u64::from(u32::MAX));
+    }
+}
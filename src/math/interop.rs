@@ -0,0 +1,389 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+// conversions to and from other math crates, each behind a cargo feature.
+//
+// Matrix uses the row vector convention (v * M, translation in m41, m42, m43)
+// while mint, glam, nalgebra and cgmath use column vectors (M * v). matrices
+// are therefore converted to their column vector equivalent (the transpose),
+// so that transforming a point gives the same result on either side.
+
+#[cfg(feature = "mint")]
+mod mint_conversions {
+    use mint;
+    use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+    impl From<Vector2> for mint::Vector2<f32> {
+        fn from(v: Vector2) -> mint::Vector2<f32> { mint::Vector2 { x: v.x, y: v.y } }
+    }
+
+    impl From<mint::Vector2<f32>> for Vector2 {
+        fn from(v: mint::Vector2<f32>) -> Vector2 { Vector2::new(v.x, v.y) }
+    }
+
+    impl From<Vector3> for mint::Vector3<f32> {
+        fn from(v: Vector3) -> mint::Vector3<f32> { mint::Vector3 { x: v.x, y: v.y, z: v.z } }
+    }
+
+    impl From<mint::Vector3<f32>> for Vector3 {
+        fn from(v: mint::Vector3<f32>) -> Vector3 { Vector3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<Vector4> for mint::Vector4<f32> {
+        fn from(v: Vector4) -> mint::Vector4<f32> { mint::Vector4 { x: v.x, y: v.y, z: v.z, w: v.w } }
+    }
+
+    impl From<mint::Vector4<f32>> for Vector4 {
+        fn from(v: mint::Vector4<f32>) -> Vector4 { Vector4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<Quaternion> for mint::Quaternion<f32> {
+        fn from(q: Quaternion) -> mint::Quaternion<f32> {
+            mint::Quaternion { v: mint::Vector3 { x: q.x, y: q.y, z: q.z }, s: q.w }
+        }
+    }
+
+    impl From<mint::Quaternion<f32>> for Quaternion {
+        fn from(q: mint::Quaternion<f32>) -> Quaternion { Quaternion::new(q.v.x, q.v.y, q.v.z, q.s) }
+    }
+
+    impl From<Matrix> for mint::ColumnMatrix4<f32> {
+        fn from(m: Matrix) -> mint::ColumnMatrix4<f32> {
+            mint::ColumnMatrix4::from(m.to_rows_array())
+        }
+    }
+
+    impl From<mint::ColumnMatrix4<f32>> for Matrix {
+        fn from(m: mint::ColumnMatrix4<f32>) -> Matrix {
+            let array: [f32; 16] = m.into();
+            Matrix::from_rows_array(array)
+        }
+    }
+
+    impl From<Matrix> for mint::RowMatrix4<f32> {
+        fn from(m: Matrix) -> mint::RowMatrix4<f32> {
+            mint::RowMatrix4::from(m.to_cols_array())
+        }
+    }
+
+    impl From<mint::RowMatrix4<f32>> for Matrix {
+        fn from(m: mint::RowMatrix4<f32>) -> Matrix {
+            let array: [f32; 16] = m.into();
+            Matrix::from_cols_array(array)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use mint;
+        use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+        #[test]
+        fn vectors_round_trip() {
+            let v2: mint::Vector2<f32> = Vector2::new(1f32, 2f32).into();
+            let v3: mint::Vector3<f32> = Vector3::new(1f32, 2f32, 3f32).into();
+            let v4: mint::Vector4<f32> = Vector4::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(Vector2::from(v2).to_array(), [1f32, 2f32]);
+            assert_eq!(Vector3::from(v3).to_array(), [1f32, 2f32, 3f32]);
+            assert_eq!(Vector4::from(v4).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            let q: mint::Quaternion<f32> = Quaternion::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(q.s, 4f32);
+            assert_eq!(Quaternion::from(q).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn matrix_round_trip() {
+            let m = Matrix::scale(Vector3::new(2f32, 3f32, 4f32)) * Matrix::translation(Vector3::new(1f32, 2f32, 3f32));
+            let columns: mint::ColumnMatrix4<f32> = m.into();
+            let rows: mint::RowMatrix4<f32> = m.into();
+            assert_eq!([columns.w.x, columns.w.y, columns.w.z], [1f32, 2f32, 3f32]);
+            assert_eq!([rows.x.w, rows.y.w, rows.z.w], [1f32, 2f32, 3f32]);
+            assert_eq!(Matrix::from(columns).to_array(), m.to_array());
+            assert_eq!(Matrix::from(rows).to_array(), m.to_array());
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_conversions {
+    use glam;
+    use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+    impl From<Vector2> for glam::Vec2 {
+        fn from(v: Vector2) -> glam::Vec2 { glam::Vec2::new(v.x, v.y) }
+    }
+
+    impl From<glam::Vec2> for Vector2 {
+        fn from(v: glam::Vec2) -> Vector2 { Vector2::new(v.x, v.y) }
+    }
+
+    impl From<Vector3> for glam::Vec3 {
+        fn from(v: Vector3) -> glam::Vec3 { glam::Vec3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<glam::Vec3> for Vector3 {
+        fn from(v: glam::Vec3) -> Vector3 { Vector3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<Vector4> for glam::Vec4 {
+        fn from(v: Vector4) -> glam::Vec4 { glam::Vec4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<glam::Vec4> for Vector4 {
+        fn from(v: glam::Vec4) -> Vector4 { Vector4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<Quaternion> for glam::Quat {
+        fn from(q: Quaternion) -> glam::Quat { glam::Quat::from_xyzw(q.x, q.y, q.z, q.w) }
+    }
+
+    impl From<glam::Quat> for Quaternion {
+        fn from(q: glam::Quat) -> Quaternion { Quaternion::new(q.x, q.y, q.z, q.w) }
+    }
+
+    impl From<Matrix> for glam::Mat4 {
+        fn from(m: Matrix) -> glam::Mat4 { glam::Mat4::from_cols_array(&m.to_rows_array()) }
+    }
+
+    impl From<glam::Mat4> for Matrix {
+        fn from(m: glam::Mat4) -> Matrix { Matrix::from_rows_array(m.to_cols_array()) }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use glam;
+        use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+        #[test]
+        fn vectors_round_trip() {
+            let v2: glam::Vec2 = Vector2::new(1f32, 2f32).into();
+            let v3: glam::Vec3 = Vector3::new(1f32, 2f32, 3f32).into();
+            let v4: glam::Vec4 = Vector4::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(Vector2::from(v2).to_array(), [1f32, 2f32]);
+            assert_eq!(Vector3::from(v3).to_array(), [1f32, 2f32, 3f32]);
+            assert_eq!(Vector4::from(v4).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            let q: glam::Quat = Quaternion::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(q.w, 4f32);
+            assert_eq!(Quaternion::from(q).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn matrix_transforms_points_identically() {
+            let m = Matrix::scale(Vector3::new(2f32, 3f32, 4f32)) * Matrix::translation(Vector3::new(1f32, 2f32, 3f32));
+            let p = Vector3::new(5f32, 6f32, 7f32);
+            let g: glam::Mat4 = m.into();
+            assert_eq!(Vector3::from(g.transform_point3(p.into())).to_array(), Vector3::transform(p, m).to_array());
+            assert_eq!(Matrix::from(g).to_array(), m.to_array());
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_conversions {
+    use nalgebra;
+    use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+    impl From<Vector2> for nalgebra::Vector2<f32> {
+        fn from(v: Vector2) -> nalgebra::Vector2<f32> { nalgebra::Vector2::new(v.x, v.y) }
+    }
+
+    impl From<nalgebra::Vector2<f32>> for Vector2 {
+        fn from(v: nalgebra::Vector2<f32>) -> Vector2 { Vector2::new(v.x, v.y) }
+    }
+
+    impl From<Vector3> for nalgebra::Vector3<f32> {
+        fn from(v: Vector3) -> nalgebra::Vector3<f32> { nalgebra::Vector3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<nalgebra::Vector3<f32>> for Vector3 {
+        fn from(v: nalgebra::Vector3<f32>) -> Vector3 { Vector3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<Vector4> for nalgebra::Vector4<f32> {
+        fn from(v: Vector4) -> nalgebra::Vector4<f32> { nalgebra::Vector4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<nalgebra::Vector4<f32>> for Vector4 {
+        fn from(v: nalgebra::Vector4<f32>) -> Vector4 { Vector4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<Quaternion> for nalgebra::Quaternion<f32> {
+        fn from(q: Quaternion) -> nalgebra::Quaternion<f32> { nalgebra::Quaternion::new(q.w, q.x, q.y, q.z) }
+    }
+
+    impl From<nalgebra::Quaternion<f32>> for Quaternion {
+        fn from(q: nalgebra::Quaternion<f32>) -> Quaternion { Quaternion::new(q.i, q.j, q.k, q.w) }
+    }
+
+    impl From<nalgebra::UnitQuaternion<f32>> for Quaternion {
+        fn from(q: nalgebra::UnitQuaternion<f32>) -> Quaternion { Quaternion::from(q.into_inner()) }
+    }
+
+    impl From<Matrix> for nalgebra::Matrix4<f32> {
+        fn from(m: Matrix) -> nalgebra::Matrix4<f32> { nalgebra::Matrix4::from_column_slice(&m.to_rows_array()) }
+    }
+
+    impl From<nalgebra::Matrix4<f32>> for Matrix {
+        fn from(m: nalgebra::Matrix4<f32>) -> Matrix {
+            let mut array = [0f32; 16];
+            array.copy_from_slice(m.as_slice());
+            Matrix::from_rows_array(array)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use nalgebra;
+        use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+        #[test]
+        fn vectors_round_trip() {
+            let v2: nalgebra::Vector2<f32> = Vector2::new(1f32, 2f32).into();
+            let v3: nalgebra::Vector3<f32> = Vector3::new(1f32, 2f32, 3f32).into();
+            let v4: nalgebra::Vector4<f32> = Vector4::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(Vector2::from(v2).to_array(), [1f32, 2f32]);
+            assert_eq!(Vector3::from(v3).to_array(), [1f32, 2f32, 3f32]);
+            assert_eq!(Vector4::from(v4).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            let q: nalgebra::Quaternion<f32> = Quaternion::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(q.w, 4f32);
+            assert_eq!(Quaternion::from(q).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn matrix_transforms_points_identically() {
+            let m = Matrix::scale(Vector3::new(2f32, 3f32, 4f32)) * Matrix::translation(Vector3::new(1f32, 2f32, 3f32));
+            let p = Vector3::new(5f32, 6f32, 7f32);
+            let n: nalgebra::Matrix4<f32> = m.into();
+            let q = n.transform_point(&nalgebra::Point3::new(p.x, p.y, p.z));
+            assert_eq!([q.x, q.y, q.z], Vector3::transform(p, m).to_array());
+            assert_eq!(Matrix::from(n).to_array(), m.to_array());
+        }
+    }
+}
+
+#[cfg(feature = "cgmath")]
+mod cgmath_conversions {
+    use cgmath;
+    use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+    impl From<Vector2> for cgmath::Vector2<f32> {
+        fn from(v: Vector2) -> cgmath::Vector2<f32> { cgmath::Vector2::new(v.x, v.y) }
+    }
+
+    impl From<cgmath::Vector2<f32>> for Vector2 {
+        fn from(v: cgmath::Vector2<f32>) -> Vector2 { Vector2::new(v.x, v.y) }
+    }
+
+    impl From<Vector3> for cgmath::Vector3<f32> {
+        fn from(v: Vector3) -> cgmath::Vector3<f32> { cgmath::Vector3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<cgmath::Vector3<f32>> for Vector3 {
+        fn from(v: cgmath::Vector3<f32>) -> Vector3 { Vector3::new(v.x, v.y, v.z) }
+    }
+
+    impl From<Vector4> for cgmath::Vector4<f32> {
+        fn from(v: Vector4) -> cgmath::Vector4<f32> { cgmath::Vector4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<cgmath::Vector4<f32>> for Vector4 {
+        fn from(v: cgmath::Vector4<f32>) -> Vector4 { Vector4::new(v.x, v.y, v.z, v.w) }
+    }
+
+    impl From<Quaternion> for cgmath::Quaternion<f32> {
+        fn from(q: Quaternion) -> cgmath::Quaternion<f32> { cgmath::Quaternion::new(q.w, q.x, q.y, q.z) }
+    }
+
+    impl From<cgmath::Quaternion<f32>> for Quaternion {
+        fn from(q: cgmath::Quaternion<f32>) -> Quaternion { Quaternion::new(q.v.x, q.v.y, q.v.z, q.s) }
+    }
+
+    impl From<Matrix> for cgmath::Matrix4<f32> {
+        fn from(m: Matrix) -> cgmath::Matrix4<f32> {
+            cgmath::Matrix4::new(m.m11, m.m12, m.m13, m.m14,
+                                 m.m21, m.m22, m.m23, m.m24,
+                                 m.m31, m.m32, m.m33, m.m34,
+                                 m.m41, m.m42, m.m43, m.m44)
+        }
+    }
+
+    impl From<cgmath::Matrix4<f32>> for Matrix {
+        fn from(m: cgmath::Matrix4<f32>) -> Matrix {
+            Matrix::new(m.x.x, m.x.y, m.x.z, m.x.w,
+                        m.y.x, m.y.y, m.y.z, m.y.w,
+                        m.z.x, m.z.y, m.z.z, m.z.w,
+                        m.w.x, m.w.y, m.w.z, m.w.w)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use cgmath;
+        use cgmath::Transform;
+        use math::{Vector2, Vector3, Vector4, Quaternion, Matrix};
+
+        #[test]
+        fn vectors_round_trip() {
+            let v2: cgmath::Vector2<f32> = Vector2::new(1f32, 2f32).into();
+            let v3: cgmath::Vector3<f32> = Vector3::new(1f32, 2f32, 3f32).into();
+            let v4: cgmath::Vector4<f32> = Vector4::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(Vector2::from(v2).to_array(), [1f32, 2f32]);
+            assert_eq!(Vector3::from(v3).to_array(), [1f32, 2f32, 3f32]);
+            assert_eq!(Vector4::from(v4).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            let q: cgmath::Quaternion<f32> = Quaternion::new(1f32, 2f32, 3f32, 4f32).into();
+            assert_eq!(q.s, 4f32);
+            assert_eq!(Quaternion::from(q).to_array(), [1f32, 2f32, 3f32, 4f32]);
+        }
+
+        #[test]
+        fn matrix_transforms_points_identically() {
+            let m = Matrix::scale(Vector3::new(2f32, 3f32, 4f32)) * Matrix::translation(Vector3::new(1f32, 2f32, 3f32));
+            let p = Vector3::new(5f32, 6f32, 7f32);
+            let c: cgmath::Matrix4<f32> = m.into();
+            let q = c.transform_point(cgmath::Point3::new(p.x, p.y, p.z));
+            assert_eq!([q.x, q.y, q.z], Vector3::transform(p, m).to_array());
+            assert_eq!(Matrix::from(c).to_array(), m.to_array());
+        }
+    }
+}
@@ -0,0 +1,302 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+
+// balanced k-d tree over a static point set. the tree is implicit: points are
+// reordered so the median of every range is its node, which keeps the built
+// structure a flat array that serializes as is.
+
+const MAGIC: &[u8; 4] = b"KDT1";
+const RECORD_SIZE: usize = 12 + 8 + 1;
+
+// index into the slice the tree was built from, and euclidean distance.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hit {
+    pub index:    usize,
+    pub distance: f32
+}
+
+pub struct KdTree {
+    points:  Vec<Vector3>,
+    indices: Vec<usize>,
+    axes:    Vec<u8>
+}
+
+impl KdTree {
+
+    pub fn new(points: &[Vector3]) -> KdTree {
+        let mut items: Vec<(Vector3, usize)> = points.iter().cloned().zip(0..points.len()).collect();
+        let mut axes = vec![0u8; points.len()];
+        build(&mut items, &mut axes);
+        KdTree {
+            points:  items.iter().map(|item| item.0).collect(),
+            indices: items.iter().map(|item| item.1).collect(),
+            axes
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn nearest(&self, point: Vector3) -> Option<Hit> {
+        self.nearest_approximate(point, 0f32)
+    }
+
+    // the result is within (1 + epsilon) of the true nearest distance.
+    pub fn nearest_approximate(&self, point: Vector3, epsilon: f32) -> Option<Hit> {
+        self.nearest_k_approximate(point, 1, epsilon).first().cloned()
+    }
+
+    // nearest first.
+    pub fn nearest_k(&self, point: Vector3, k: usize) -> Vec<Hit> {
+        self.nearest_k_approximate(point, k, 0f32)
+    }
+
+    pub fn nearest_k_approximate(&self, point: Vector3, k: usize, epsilon: f32) -> Vec<Hit> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let scale = 1f32 / ((1f32 + epsilon) * (1f32 + epsilon));
+        let mut best: Vec<(f32, usize)> = Vec::with_capacity(k + 1);
+        self.search_k(0, self.points.len(), point, k, scale, &mut best);
+        best.into_iter().map(|(distance_squared, i)| Hit {
+            index:    self.indices[i],
+            distance: distance_squared.sqrt()
+        }).collect()
+    }
+
+    // nearest first.
+    pub fn within_radius(&self, point: Vector3, radius: f32) -> Vec<Hit> {
+        let mut found: Vec<(f32, usize)> = Vec::new();
+        self.search_radius(0, self.points.len(), point, radius * radius, &mut found);
+        found.sort_by(|a, b| a.0.total_cmp(&b.0));
+        found.into_iter().map(|(distance_squared, i)| Hit {
+            index:    self.indices[i],
+            distance: distance_squared.sqrt()
+        }).collect()
+    }
+
+    // little endian: magic, point count as u64, then per node x, y, z as f32,
+    // the source index as u64 and the split axis as u8.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12 + (self.points.len() * RECORD_SIZE));
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&(self.points.len() as u64).to_le_bytes());
+        for ((point, index), axis) in self.points.iter().zip(&self.indices).zip(&self.axes) {
+            bytes.extend_from_slice(&point.x.to_le_bytes());
+            bytes.extend_from_slice(&point.y.to_le_bytes());
+            bytes.extend_from_slice(&point.z.to_le_bytes());
+            bytes.extend_from_slice(&(*index as u64).to_le_bytes());
+            bytes.push(*axis);
+        }
+        bytes
+    }
+
+    // none when the bytes are not a tree written by to_bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<KdTree> {
+        if bytes.len() < 12 || &bytes[0..4] != MAGIC {
+            return None;
+        }
+        let count = u64::from_le_bytes(read(&bytes[4..12])) as usize;
+        if bytes.len() - 12 != count.checked_mul(RECORD_SIZE)? {
+            return None;
+        }
+        let mut tree = KdTree {
+            points:  Vec::with_capacity(count),
+            indices: Vec::with_capacity(count),
+            axes:    Vec::with_capacity(count)
+        };
+        for record in bytes[12..].chunks(RECORD_SIZE) {
+            let axis = record[20];
+            if axis > 2 {
+                return None;
+            }
+            tree.points.push(Vector3::new(
+                f32::from_le_bytes(read(&record[0..4])),
+                f32::from_le_bytes(read(&record[4..8])),
+                f32::from_le_bytes(read(&record[8..12]))));
+            tree.indices.push(u64::from_le_bytes(read(&record[12..20])) as usize);
+            tree.axes.push(axis);
+        }
+        Some(tree)
+    }
+
+    fn search_k(&self, start: usize, end: usize, point: Vector3, k: usize, scale: f32, best: &mut Vec<(f32, usize)>) {
+        if start >= end {
+            return;
+        }
+        let mid  = start + ((end - start) / 2);
+        let node = self.points[mid];
+        let distance_squared = (node - point).length_squared();
+        if best.len() < k || distance_squared < best[best.len() - 1].0 {
+            let position = best.partition_point(|b| b.0 <= distance_squared);
+            best.insert(position, (distance_squared, mid));
+            best.truncate(k);
+        }
+        let axis  = self.axes[mid] as usize;
+        let delta = point[axis] - node[axis];
+        let (near, far) = if delta < 0f32 { ((start, mid), (mid + 1, end)) } else { ((mid + 1, end), (start, mid)) };
+        self.search_k(near.0, near.1, point, k, scale, best);
+        if best.len() < k || (delta * delta) < best[best.len() - 1].0 * scale {
+            self.search_k(far.0, far.1, point, k, scale, best);
+        }
+    }
+
+    fn search_radius(&self, start: usize, end: usize, point: Vector3, radius_squared: f32, found: &mut Vec<(f32, usize)>) {
+        if start >= end {
+            return;
+        }
+        let mid  = start + ((end - start) / 2);
+        let node = self.points[mid];
+        let distance_squared = (node - point).length_squared();
+        if distance_squared <= radius_squared {
+            found.push((distance_squared, mid));
+        }
+        let axis  = self.axes[mid] as usize;
+        let delta = point[axis] - node[axis];
+        if delta <= 0f32 || (delta * delta) <= radius_squared {
+            self.search_radius(start, mid, point, radius_squared, found);
+        }
+        if delta >= 0f32 || (delta * delta) <= radius_squared {
+            self.search_radius(mid + 1, end, point, radius_squared, found);
+        }
+    }
+}
+
+// splits on the axis of greatest spread, placing the median at the middle.
+fn build(items: &mut [(Vector3, usize)], axes: &mut [u8]) {
+    if items.len() <= 1 {
+        return;
+    }
+    let mut min = items[0].0;
+    let mut max = items[0].0;
+    for item in items.iter() {
+        min = Vector3::min(min, item.0);
+        max = Vector3::max(max, item.0);
+    }
+    let size = max - min;
+    let axis = if size.x >= size.y && size.x >= size.z { 0 } else if size.y >= size.z { 1 } else { 2 };
+    let mid  = items.len() / 2;
+    items.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    axes[mid] = axis as u8;
+    let (left, right)           = items.split_at_mut(mid);
+    let (left_axes, right_axes) = axes.split_at_mut(mid);
+    build(left, left_axes);
+    build(&mut right[1..], &mut right_axes[1..]);
+}
+
+fn read<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut array = [0u8; N];
+    array.copy_from_slice(bytes);
+    array
+}
+
+#[cfg(test)]
+mod tests {
+    use math::Vector3;
+    use super::KdTree;
+
+    fn points(count: usize) -> Vec<Vector3> {
+        (0..count).map(|i| {
+            let t = i as f32;
+            Vector3::new((t * 7.31f32) % 50f32 - 25f32, (t * 3.17f32) % 40f32 - 20f32, (t * 1.93f32) % 30f32 - 15f32)
+        }).collect()
+    }
+
+    fn sorted_distances(points: &[Vector3], query: Vector3) -> Vec<f32> {
+        let mut distances: Vec<f32> = points.iter().map(|p| (*p - query).length_squared().sqrt()).collect();
+        distances.sort_by(|a, b| a.total_cmp(b));
+        distances
+    }
+
+    fn queries() -> Vec<Vector3> {
+        (0..25).map(|i| Vector3::new((i as f32 * 4.1f32) - 50f32, (i as f32 * 1.7f32) - 20f32, 3f32 - (i as f32))).collect()
+    }
+
+    #[test]
+    fn nearest_matches_brute_force() {
+        let points = points(500);
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.len(), 500);
+        for query in queries() {
+            let expected = sorted_distances(&points, query);
+            let hit = tree.nearest(query).unwrap();
+            assert_eq!(hit.distance, expected[0]);
+            assert_eq!((points[hit.index] - query).length_squared().sqrt(), hit.distance);
+            let hits = tree.nearest_k(query, 12);
+            let distances: Vec<f32> = hits.iter().map(|h| h.distance).collect();
+            assert_eq!(distances, expected[..12].to_vec());
+            assert_eq!(tree.nearest_k(query, 1000).len(), 500);
+        }
+        assert!(KdTree::new(&[]).nearest(Vector3::zero()).is_none());
+    }
+
+    #[test]
+    fn approximate_nearest_is_within_epsilon() {
+        let points = points(500);
+        let tree = KdTree::new(&points);
+        for query in queries() {
+            let exact = tree.nearest(query).unwrap().distance;
+            let approximate = tree.nearest_approximate(query, 0.5f32).unwrap().distance;
+            assert!(approximate >= exact && approximate <= exact * 1.5f32 + 1e-6f32);
+        }
+    }
+
+    #[test]
+    fn within_radius_matches_brute_force() {
+        let points = points(500);
+        let tree = KdTree::new(&points);
+        for query in queries() {
+            let hits = tree.within_radius(query, 7f32);
+            let expected: Vec<f32> = sorted_distances(&points, query).into_iter().filter(|d| *d <= 7f32).collect();
+            assert_eq!(hits.iter().map(|h| h.distance).collect::<Vec<f32>>(), expected);
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let points = points(100);
+        let tree  = KdTree::new(&points);
+        let bytes = tree.to_bytes();
+        let read  = KdTree::from_bytes(&bytes).unwrap();
+        for query in queries() {
+            assert_eq!(read.nearest_k(query, 5), tree.nearest_k(query, 5));
+        }
+        assert!(KdTree::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(KdTree::from_bytes(b"nope").is_none());
+        let mut bad_axis = bytes.clone();
+        bad_axis[12 + 20] = 3;
+        assert!(KdTree::from_bytes(&bad_axis).is_none());
+    }
+}
@@ -0,0 +1,194 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::mem::{size_of, align_of};
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::vector4::Vector4;
+use super::quaternion::Quaternion;
+use super::matrix::Matrix;
+use super::plane::Plane;
+use super::color::{Color, PackedColor};
+
+// a vec2 member of a block: 8 bytes, 8 byte aligned in std140 and std430.
+#[repr(C, align(8))]
+#[derive(Copy, Clone)]
+pub struct Std140Vector2 {
+    pub x: f32,
+    pub y: f32
+}
+
+// an element of a vec2 array, padded to the 16 byte array stride std140
+// requires. std430 arrays use Std430Vector2 with a stride of 8.
+#[repr(C, align(16))]
+#[derive(Copy, Clone)]
+pub struct Std140Vector2Element {
+    pub x: f32,
+    pub y: f32,
+    padding: [f32; 2]
+}
+
+// a vec3 padded to 16 bytes, matching both std140 and std430.
+#[repr(C, align(16))]
+#[derive(Copy, Clone)]
+pub struct Std140Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    padding: f32
+}
+
+// a column-major mat4 for shaders using column vectors (M * v). as this crate
+// multiplies row vectors, each column here is a row of the source Matrix, so
+// the floats are in the same order as Matrix::to_array().
+#[repr(C, align(16))]
+#[derive(Copy, Clone)]
+pub struct Std140Matrix {
+    pub columns: [[f32; 4]; 4]
+}
+
+// intentionally the std140 types: std430 only drops the rounding of array
+// and struct strides up to 16 bytes, which leaves vec2, vec3 and mat4 laid
+// out exactly as in std140.
+pub type Std430Vector2 = Std140Vector2;
+pub type Std430Vector3 = Std140Vector3;
+pub type Std430Matrix  = Std140Matrix;
+
+impl From<Vector2> for Std140Vector2 {
+    fn from(value: Vector2) -> Std140Vector2 {
+        Std140Vector2 {
+            x: value.x,
+            y: value.y
+        }
+    }
+}
+
+impl From<Std140Vector2> for Vector2 {
+    fn from(value: Std140Vector2) -> Vector2 {
+        Vector2::new(value.x, value.y)
+    }
+}
+
+impl From<Vector2> for Std140Vector2Element {
+    fn from(value: Vector2) -> Std140Vector2Element {
+        Std140Vector2Element {
+            x: value.x,
+            y: value.y,
+            padding: [0f32; 2]
+        }
+    }
+}
+
+impl From<Std140Vector2Element> for Vector2 {
+    fn from(value: Std140Vector2Element) -> Vector2 {
+        Vector2::new(value.x, value.y)
+    }
+}
+
+impl From<Vector3> for Std140Vector3 {
+    fn from(value: Vector3) -> Std140Vector3 {
+        Std140Vector3 {
+            x: value.x,
+            y: value.y,
+            z: value.z,
+            padding: 0f32
+        }
+    }
+}
+
+impl From<Std140Vector3> for Vector3 {
+    fn from(value: Std140Vector3) -> Vector3 {
+        Vector3::new(value.x, value.y, value.z)
+    }
+}
+
+impl From<Matrix> for Std140Matrix {
+    fn from(m: Matrix) -> Std140Matrix {
+        Std140Matrix {
+            columns: [[m.m11, m.m12, m.m13, m.m14],
+                      [m.m21, m.m22, m.m23, m.m24],
+                      [m.m31, m.m32, m.m33, m.m34],
+                      [m.m41, m.m42, m.m43, m.m44]]
+        }
+    }
+}
+
+impl From<Std140Matrix> for Matrix {
+    fn from(value: Std140Matrix) -> Matrix {
+        let c = value.columns;
+        Matrix::new(c[0][0], c[0][1], c[0][2], c[0][3],
+                    c[1][0], c[1][1], c[1][2], c[1][3],
+                    c[2][0], c[2][1], c[2][2], c[2][3],
+                    c[3][0], c[3][1], c[3][2], c[3][3])
+    }
+}
+
+const _: () = assert!(size_of::<Vector2>()              ==  8 && align_of::<Vector2>()              ==  4);
+const _: () = assert!(size_of::<Vector3>()              == 12 && align_of::<Vector3>()              ==  4);
+const _: () = assert!(size_of::<Vector4>()              == 16 && align_of::<Vector4>()              ==  4);
+const _: () = assert!(size_of::<Quaternion>()           == 16 && align_of::<Quaternion>()           ==  4);
+const _: () = assert!(size_of::<Matrix>()               == 64 && align_of::<Matrix>()               ==  4);
+const _: () = assert!(size_of::<Plane>()                == 16 && align_of::<Plane>()                ==  4);
+const _: () = assert!(size_of::<Color>()                == 16 && align_of::<Color>()                ==  4);
+const _: () = assert!(size_of::<PackedColor>()          ==  4 && align_of::<PackedColor>()          ==  1);
+const _: () = assert!(size_of::<Std140Vector2>()        ==  8 && align_of::<Std140Vector2>()        ==  8);
+const _: () = assert!(size_of::<Std140Vector2Element>() == 16 && align_of::<Std140Vector2Element>() == 16);
+const _: () = assert!(size_of::<Std140Vector3>()        == 16 && align_of::<Std140Vector3>()        == 16);
+const _: () = assert!(size_of::<Std140Matrix>()         == 64 && align_of::<Std140Matrix>()         == 16);
+
+#[cfg(feature = "bytemuck")]
+mod pod {
+    use bytemuck::{Pod, Zeroable};
+    use super::*;
+
+    unsafe impl Zeroable for Vector2              {}
+    unsafe impl Zeroable for Vector3              {}
+    unsafe impl Zeroable for Vector4              {}
+    unsafe impl Zeroable for Quaternion           {}
+    unsafe impl Zeroable for Matrix               {}
+    unsafe impl Zeroable for Plane                {}
+    unsafe impl Zeroable for Color                {}
+    unsafe impl Zeroable for PackedColor          {}
+    unsafe impl Zeroable for Std140Vector2        {}
+    unsafe impl Zeroable for Std140Vector2Element {}
+    unsafe impl Zeroable for Std140Vector3        {}
+    unsafe impl Zeroable for Std140Matrix         {}
+
+    unsafe impl Pod for Vector2              {}
+    unsafe impl Pod for Vector3              {}
+    unsafe impl Pod for Vector4              {}
+    unsafe impl Pod for Quaternion           {}
+    unsafe impl Pod for Matrix               {}
+    unsafe impl Pod for Plane                {}
+    unsafe impl Pod for Color                {}
+    unsafe impl Pod for PackedColor          {}
+    unsafe impl Pod for Std140Vector2        {}
+    unsafe impl Pod for Std140Vector2Element {}
+    unsafe impl Pod for Std140Vector3        {}
+    unsafe impl Pod for Std140Matrix         {}
+}
@@ -0,0 +1,216 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+// boilerplate operator impls derived from the by-value operators each type
+// writes out explicitly.
+
+macro_rules! impl_assign_op {
+    ($t:ty, $rhs:ty, $trait:ident, $method:ident, $op:tt) => {
+        impl ::std::ops::$trait<$rhs> for $t {
+            fn $method(&mut self, rhs: $rhs) {
+                *self = *self $op rhs;
+            }
+        }
+    }
+}
+
+macro_rules! impl_ref_binop {
+    ($t:ty, $rhs:ty, $trait:ident, $method:ident) => {
+        impl<'a> ::std::ops::$trait<&'a $rhs> for $t {
+            type Output = <$t as ::std::ops::$trait<$rhs>>::Output;
+            fn $method(self, rhs: &'a $rhs) -> Self::Output {
+                ::std::ops::$trait::$method(self, *rhs)
+            }
+        }
+        impl<'a> ::std::ops::$trait<$rhs> for &'a $t {
+            type Output = <$t as ::std::ops::$trait<$rhs>>::Output;
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                ::std::ops::$trait::$method(*self, rhs)
+            }
+        }
+        impl<'a, 'b> ::std::ops::$trait<&'b $rhs> for &'a $t {
+            type Output = <$t as ::std::ops::$trait<$rhs>>::Output;
+            fn $method(self, rhs: &'b $rhs) -> Self::Output {
+                ::std::ops::$trait::$method(*self, *rhs)
+            }
+        }
+    }
+}
+
+macro_rules! impl_ref_neg {
+    ($t:ty) => {
+        impl<'a> ::std::ops::Neg for &'a $t {
+            type Output = $t;
+            fn neg(self) -> $t {
+                -*self
+            }
+        }
+    }
+}
+
+macro_rules! impl_scalar_mul {
+    ($t:ty) => {
+        impl ::std::ops::Mul<$t> for f32 {
+            type Output = $t;
+            fn mul(self, rhs: $t) -> $t {
+                rhs * self
+            }
+        }
+        impl_ref_binop!(f32, $t, Mul, mul);
+    }
+}
+
+// the full set of arithmetic operators for a type implementing
+// Add, Sub, Mul and Div against itself and f32, and Neg.
+macro_rules! impl_arithmetic_ops {
+    ($t:ty) => {
+        impl_assign_op!($t, $t,  AddAssign, add_assign, +);
+        impl_assign_op!($t, $t,  SubAssign, sub_assign, -);
+        impl_assign_op!($t, $t,  MulAssign, mul_assign, *);
+        impl_assign_op!($t, f32, MulAssign, mul_assign, *);
+        impl_assign_op!($t, $t,  DivAssign, div_assign, /);
+        impl_assign_op!($t, f32, DivAssign, div_assign, /);
+        impl_ref_binop!($t, $t,  Add, add);
+        impl_ref_binop!($t, $t,  Sub, sub);
+        impl_ref_binop!($t, $t,  Mul, mul);
+        impl_ref_binop!($t, f32, Mul, mul);
+        impl_ref_binop!($t, $t,  Div, div);
+        impl_ref_binop!($t, f32, Div, div);
+        impl_ref_neg!($t);
+        impl_scalar_mul!($t);
+    }
+}
+
+macro_rules! impl_sum_product {
+    ($t:ty, $zero:expr, $one:expr) => {
+        impl ::std::iter::Sum for $t {
+            fn sum<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold($zero, |a, b| a + b)
+            }
+        }
+        impl<'a> ::std::iter::Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold($zero, |a, b| a + *b)
+            }
+        }
+        impl_product!($t, $one);
+    }
+}
+
+macro_rules! impl_product {
+    ($t:ty, $one:expr) => {
+        impl ::std::iter::Product for $t {
+            fn product<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold($one, |a, b| a * b)
+            }
+        }
+        impl<'a> ::std::iter::Product<&'a $t> for $t {
+            fn product<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold($one, |a, b| a * *b)
+            }
+        }
+    }
+}
+
+// component indexing through the type's as_array / as_mut_array.
+macro_rules! impl_component_index {
+    ($t:ty) => {
+        impl ::std::ops::Index<usize> for $t {
+            type Output = f32;
+            fn index(&self, index: usize) -> &f32 {
+                &self.as_array()[index]
+            }
+        }
+        impl ::std::ops::IndexMut<usize> for $t {
+            fn index_mut(&mut self, index: usize) -> &mut f32 {
+                &mut self.as_mut_array()[index]
+            }
+        }
+    }
+}
+
+// component-wise helpers shared by the vector types.
+macro_rules! impl_component_wise {
+    ($t:ident, $($c:ident),+) => {
+        impl $t {
+
+            pub fn map<F: Fn(f32) -> f32>(&self, f: F) -> $t {
+                $t { $($c: f(self.$c)),+ }
+            }
+
+            pub fn zip_with<F: Fn(f32, f32) -> f32>(&self, other: $t, f: F) -> $t {
+                $t { $($c: f(self.$c, other.$c)),+ }
+            }
+
+            pub fn abs    (&self) -> $t { self.map(f32::abs) }
+            pub fn floor  (&self) -> $t { self.map(f32::floor) }
+            pub fn ceil   (&self) -> $t { self.map(f32::ceil) }
+            pub fn round  (&self) -> $t { self.map(f32::round) }
+            pub fn signum (&self) -> $t { self.map(f32::signum) }
+
+            // glsl fract, x - floor(x), which is non-negative for negative inputs.
+            pub fn fract(&self) -> $t {
+                self.map(|value| value - value.floor())
+            }
+
+            pub fn min_element(&self) -> f32 {
+                self.as_array().iter().cloned().fold(f32::INFINITY, f32::min)
+            }
+
+            pub fn max_element(&self) -> f32 {
+                self.as_array().iter().cloned().fold(f32::NEG_INFINITY, f32::max)
+            }
+
+            pub fn sum(&self) -> f32 {
+                self.as_array().iter().sum()
+            }
+
+            pub fn product(&self) -> f32 {
+                self.as_array().iter().product()
+            }
+        }
+    }
+}
+
+// component-wise comparisons returning a boolean mask, and mask selection.
+macro_rules! impl_comparisons {
+    ($t:ident, $mask:ident, $($c:ident),+) => {
+        impl $t {
+            pub fn cmpeq(&self, other: $t) -> $mask { $mask { $($c: self.$c == other.$c),+ } }
+            pub fn cmpne(&self, other: $t) -> $mask { $mask { $($c: self.$c != other.$c),+ } }
+            pub fn cmplt(&self, other: $t) -> $mask { $mask { $($c: self.$c <  other.$c),+ } }
+            pub fn cmple(&self, other: $t) -> $mask { $mask { $($c: self.$c <= other.$c),+ } }
+            pub fn cmpgt(&self, other: $t) -> $mask { $mask { $($c: self.$c >  other.$c),+ } }
+            pub fn cmpge(&self, other: $t) -> $mask { $mask { $($c: self.$c >= other.$c),+ } }
+
+            pub fn select(mask: $mask, if_true: $t, if_false: $t) -> $t {
+                $t { $($c: if mask.$c { if_true.$c } else { if_false.$c }),+ }
+            }
+        }
+    }
+}
@@ -26,13 +26,16 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-use std::ops::{Add, Sub, Mul, Div};
+use std::ops::{Add, Sub, Mul, Div, Neg, Index, IndexMut};
 use std::fmt::{Display, Formatter, Error};
 use std::num;
 use super::vector3::Vector3;
+use super::vector4::Vector4;
 use super::plane::Plane;
 use super::quaternion:: Quaternion;
+use super::angle::Radians;
 
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Matrix {
     pub m11: f32, pub m12: f32, pub m13: f32, pub m14: f32,
@@ -135,6 +138,47 @@ impl Div<f32> for Matrix {
     }
 }
 
+impl Neg for Matrix {
+    type Output = Matrix;
+    fn neg(self) -> Matrix {
+        Matrix::negate(self)
+    }
+}
+
+// the column product M * v, which is the same as v * transpose(M). points are
+// transformed with the row vector product v * M instead, see Vector4 and
+// Vector3::transform.
+impl Mul<Vector4> for Matrix {
+    type Output = Vector4;
+    fn mul(self, rhs: Vector4) -> Vector4 {
+        Vector4 {
+            x: (((self.m11 * rhs.x) + (self.m12 * rhs.y)) + (self.m13 * rhs.z)) + (self.m14 * rhs.w),
+            y: (((self.m21 * rhs.x) + (self.m22 * rhs.y)) + (self.m23 * rhs.z)) + (self.m24 * rhs.w),
+            z: (((self.m31 * rhs.x) + (self.m32 * rhs.y)) + (self.m33 * rhs.z)) + (self.m34 * rhs.w),
+            w: (((self.m41 * rhs.x) + (self.m42 * rhs.y)) + (self.m43 * rhs.z)) + (self.m44 * rhs.w)
+        }
+    }
+}
+
+impl_arithmetic_ops!(Matrix);
+impl_ref_binop!(Matrix, Vector4, Mul, mul);
+impl_sum_product!(Matrix, Matrix::ZERO, Matrix::IDENTITY);
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f32;
+    fn index(&self, (row, col): (usize, usize)) -> &f32 {
+        assert!(row < 4 && col < 4, "matrix index ({}, {}) out of range", row, col);
+        &self.as_array()[(row * 4) + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f32 {
+        assert!(row < 4 && col < 4, "matrix index ({}, {}) out of range", row, col);
+        &mut self.as_mut_array()[(row * 4) + col]
+    }
+}
+
 impl Display for Matrix {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "({}, {}, {}, {}, \n {}, {}, {}, {}, \n {}, {}, {}, {}, \n {}, {}, {}, {})", 
@@ -147,6 +191,10 @@ impl Display for Matrix {
 
 impl Matrix {
 
+    pub const IDENTITY: Matrix = Matrix::identity();
+    pub const ZERO:     Matrix = Matrix::zero();
+    pub const ONE:      Matrix = Matrix::one();
+
     pub fn up       (&self) -> Vector3 { Vector3::new( self.m21,  self.m22,  self.m23) }
     pub fn down     (&self) -> Vector3 { Vector3::new(-self.m21, -self.m22, -self.m23) }
     pub fn right    (&self) -> Vector3 { Vector3::new( self.m11,  self.m12,  self.m13) }
@@ -154,7 +202,7 @@ impl Matrix {
     pub fn forward  (&self) -> Vector3 { Vector3::new(-self.m31, -self.m32, -self.m33) }
     pub fn backward (&self) -> Vector3 { Vector3::new( self.m31,  self.m32,  self.m33) }
 
-    pub fn new (m11: f32, m12: f32, m13: f32, m14: f32,
+    pub const fn new (m11: f32, m12: f32, m13: f32, m14: f32,
                 m21: f32, m22: f32, m23: f32, m24: f32,
                 m31: f32, m32: f32, m33: f32, m34: f32,
                 m41: f32, m42: f32, m43: f32, m44: f32) -> Matrix {
@@ -166,7 +214,91 @@ impl Matrix {
         }
     }
 
-    pub fn identity() -> Matrix {
+    pub const fn from_array(array: [f32; 16]) -> Matrix {
+        Matrix::new(array[0], array[1], array[2], array[3],
+                    array[4], array[5], array[6], array[7],
+                    array[8], array[9], array[10], array[11],
+                    array[12], array[13], array[14], array[15])
+    }
+
+    pub const fn to_array(self) -> [f32; 16] {
+        [self.m11, self.m12, self.m13, self.m14,
+         self.m21, self.m22, self.m23, self.m24,
+         self.m31, self.m32, self.m33, self.m34,
+         self.m41, self.m42, self.m43, self.m44]
+    }
+
+    pub fn as_array(&self) -> &[f32; 16] {
+        unsafe { &*(self as *const Matrix as *const [f32; 16]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 16] {
+        unsafe { &mut *(self as *mut Matrix as *mut [f32; 16]) }
+    }
+
+    // Matrix follows the row vector convention: vectors multiply on the left
+    // (v * M) and the translation lives in m41, m42, m43. the equivalent
+    // column vector matrix (M * v, as used by opengl and gltf) is the
+    // transpose, so the column-major array such apis expect is
+    // to_rows_array(), and to_cols_array() is that of the transpose.
+
+    pub const fn from_rows_array(array: [f32; 16]) -> Matrix {
+        Matrix::from_array(array)
+    }
+
+    pub const fn to_rows_array(self) -> [f32; 16] {
+        self.to_array()
+    }
+
+    pub const fn from_cols_array(array: [f32; 16]) -> Matrix {
+        Matrix::new(array[0], array[4], array[8],  array[12],
+                    array[1], array[5], array[9],  array[13],
+                    array[2], array[6], array[10], array[14],
+                    array[3], array[7], array[11], array[15])
+    }
+
+    pub const fn to_cols_array(self) -> [f32; 16] {
+        [self.m11, self.m21, self.m31, self.m41,
+         self.m12, self.m22, self.m32, self.m42,
+         self.m13, self.m23, self.m33, self.m43,
+         self.m14, self.m24, self.m34, self.m44]
+    }
+
+    pub fn to_column_vector_convention(self) -> Matrix {
+        Matrix::transpose(self)
+    }
+
+    pub fn from_column_vector_convention(matrix: Matrix) -> Matrix {
+        Matrix::transpose(matrix)
+    }
+
+    pub fn row(&self, index: usize) -> Vector4 {
+        Vector4::new(self[(index, 0)], self[(index, 1)], self[(index, 2)], self[(index, 3)])
+    }
+
+    pub fn col(&self, index: usize) -> Vector4 {
+        Vector4::new(self[(0, index)], self[(1, index)], self[(2, index)], self[(3, index)])
+    }
+
+    pub fn set_row(&mut self, index: usize, value: Vector4) {
+        self[(index, 0)] = value.x;
+        self[(index, 1)] = value.y;
+        self[(index, 2)] = value.z;
+        self[(index, 3)] = value.w;
+    }
+
+    pub fn set_col(&mut self, index: usize, value: Vector4) {
+        self[(0, index)] = value.x;
+        self[(1, index)] = value.y;
+        self[(2, index)] = value.z;
+        self[(3, index)] = value.w;
+    }
+
+    pub const fn identity() -> Matrix {
         Matrix {
             m11: 1f32, m12: 0f32, m13: 0f32, m14: 0f32,
             m21: 0f32, m22: 1f32, m23: 0f32, m24: 0f32,
@@ -175,7 +307,7 @@ impl Matrix {
         }
     }
 
-    pub fn zero() -> Matrix {
+    pub const fn zero() -> Matrix {
         Matrix {
             m11: 0f32, m12: 0f32, m13: 0f32, m14: 0f32,
             m21: 0f32, m22: 0f32, m23: 0f32, m24: 0f32,
@@ -184,7 +316,7 @@ impl Matrix {
         }
     }
     
-    pub fn one() -> Matrix {
+    pub const fn one() -> Matrix {
         Matrix {
             m11: 1f32, m12: 1f32, m13: 1f32, m14: 1f32,
             m21: 1f32, m22: 1f32, m23: 1f32, m24: 1f32,
@@ -193,7 +325,7 @@ impl Matrix {
         }
     }
 
-    pub fn translation(v: Vector3) -> Matrix {
+    pub const fn translation(v: Vector3) -> Matrix {
         Matrix {
             m11: 1f32, m12: 0f32, m13: 0f32, m14: 0f32,
             m21: 0f32, m22: 1f32, m23: 0f32, m24: 0f32,
@@ -202,7 +334,7 @@ impl Matrix {
         }
     }
 
-    pub fn scale(v: Vector3) -> Matrix {
+    pub const fn scale(v: Vector3) -> Matrix {
         Matrix {
             m11: v.x , m12: 0f32, m13: 0f32, m14: 0f32,
             m21: 0f32, m22: v.y , m23: 0f32, m24: 0f32,
@@ -211,7 +343,8 @@ impl Matrix {
         }
     }
 
-    pub fn rotation_x(radian: f32) -> Matrix {
+    pub fn rotation_x(radian: impl Into<Radians>) -> Matrix {
+        let radian = radian.into().0;
         let num2 = radian.cos();
         let num  = radian.sin();
         Matrix {
@@ -222,7 +355,8 @@ impl Matrix {
         }
     }
 
-    pub fn rotation_y(radian: f32) -> Matrix {
+    pub fn rotation_y(radian: impl Into<Radians>) -> Matrix {
+        let radian = radian.into().0;
         let num2 = radian.cos();
         let num = radian.sin();
         Matrix {
@@ -233,7 +367,8 @@ impl Matrix {
         }
     }
 
-    pub fn rotation_z(radian: f32) -> Matrix {
+    pub fn rotation_z(radian: impl Into<Radians>) -> Matrix {
+        let radian = radian.into().0;
         let num2 = radian.cos();
         let num = radian.sin();
         Matrix {
@@ -244,7 +379,8 @@ impl Matrix {
         }
     }
 
-    pub fn rotation_axis(axis: Vector3, radian: f32) -> Matrix {
+    pub fn rotation_axis(axis: Vector3, radian: impl Into<Radians>) -> Matrix {
+        let radian = radian.into().0;
         let x     = axis.x;
         let y     = axis.y;
         let z     = axis.z;
@@ -297,7 +433,8 @@ impl Matrix {
         }
     }
 
-    pub fn perspective_fov(fov: f32, aspect: f32, near_plane: f32, far_plane: f32) -> Matrix {
+    pub fn perspective_fov(fov: impl Into<Radians>, aspect: f32, near_plane: f32, far_plane: f32) -> Matrix {
+        let fov = fov.into().0;
         let num  = 1f32 / (fov * 0.5f32).tan();
         let num9 = num / aspect;
         Matrix {
@@ -461,8 +598,9 @@ impl Matrix {
         } 
     }
 
-    pub fn from_yaw_pitch_roll(yaw: f32, pitch: f32, roll: f32) -> Matrix {
-        Matrix::identity()
+    // roll about z, then pitch about x, then yaw about y.
+    pub fn from_yaw_pitch_roll(yaw: impl Into<Radians>, pitch: impl Into<Radians>, roll: impl Into<Radians>) -> Matrix {
+        Matrix::from_quaternion(Quaternion::from_yaw_pitch_roll(yaw, pitch, roll))
     }
 
     pub fn shadow(lightDirection: Vector3, plane: Plane) -> Matrix {
@@ -524,6 +662,11 @@ impl Matrix {
         }
     }
 
+    // same as matrix * vector.
+    pub fn transform_column(matrix: Matrix, vector: Vector4) -> Vector4 {
+        matrix * vector
+    }
+
     pub fn determinant(matrix: Matrix) -> f32 {
         let num22 = matrix.m11;
         let num21 = matrix.m12;
@@ -666,3 +809,38 @@ impl Matrix {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Vector4, Quaternion, Matrix, Degrees};
+
+    #[test]
+    fn yaw_pitch_roll_applies_roll_then_pitch_then_yaw() {
+        let (yaw, pitch, roll) = (Degrees(30f32), Degrees(-50f32), Degrees(75f32));
+        let matrix = Matrix::from_yaw_pitch_roll(yaw, pitch, roll);
+        for &v in &[Vector3::UNIT_X, Vector3::UNIT_Y, Vector3::UNIT_Z, Vector3::new(1f32, -2f32, 3f32)] {
+            let rolled  = Vector3::transform_quaternion(v, Quaternion::from_axis_angle(Vector3::UNIT_Z, roll));
+            let pitched = Vector3::transform_quaternion(rolled, Quaternion::from_axis_angle(Vector3::UNIT_X, pitch));
+            let yawed   = Vector3::transform_quaternion(pitched, Quaternion::from_axis_angle(Vector3::UNIT_Y, yaw));
+            assert!((Vector3::transform_normal(v, matrix) - yawed).length() < 1e-5f32);
+        }
+    }
+
+    #[test]
+    fn column_product_is_the_transposed_row_product() {
+        let matrix = Matrix::from_yaw_pitch_roll(Degrees(10f32), Degrees(20f32), Degrees(30f32)) * Matrix::translation(Vector3::new(1f32, 2f32, 3f32));
+        let vector = Vector4::new(1f32, -2f32, 3f32, 1f32);
+        let column = matrix * vector;
+        let row    = vector * Matrix::transpose(matrix);
+        assert!(column.as_array().iter().zip(row.as_array()).all(|(a, b)| (a - b).abs() < 1e-5f32));
+        let (matrix_ref, vector_ref) = (&matrix, &vector);
+        assert!((matrix_ref * vector_ref).as_array() == column.as_array());
+        assert!(Matrix::transform_column(matrix, vector).as_array() == column.as_array());
+    }
+
+    #[test]
+    fn yaw_turns_x_towards_negative_z() {
+        let matrix = Matrix::from_yaw_pitch_roll(Degrees(90f32), Degrees(0f32), Degrees(0f32));
+        assert!((Vector3::transform_normal(Vector3::UNIT_X, matrix) - Vector3::new(0f32, 0f32, -1f32)).length() < 1e-6f32);
+    }
+}
@@ -26,22 +26,60 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
+#[macro_use]
+mod macros;
+
 mod vector2;
 mod vector3;
 mod vector4;
+mod swizzle;
+mod bool_vector;
+mod integer_vector;
+mod angle;
 mod quaternion;
 mod matrix;
 mod plane;
 mod bounding_box;
 mod bounding_sphere;
 mod bounding_frustum;
+mod oriented_bounding_box;
+mod capsule;
+mod cylinder;
+mod triangle;
+mod polygon;
+mod color;
+mod viewport;
+mod layout;
+mod interop;
+pub mod scalar;
+pub mod query;
+pub mod curve;
+pub mod bvh;
+pub mod octree;
+pub mod spatial_hash;
+pub mod kd_tree;
+pub mod convex_hull;
+pub mod gjk;
+pub mod sweep;
+pub mod camera;
 
 pub use self::vector2::Vector2;
 pub use self::vector3::Vector3;
 pub use self::vector4::Vector4;
+pub use self::bool_vector::{BVector2, BVector3, BVector4};
+pub use self::integer_vector::{IVector2, IVector3, UVector2, UVector3};
 pub use self::quaternion::Quaternion;
+pub use self::angle::{Radians, Degrees};
 pub use self::matrix::Matrix;
-pub use self::plane::Plane;
+pub use self::plane::{Plane, IntersectionType};
 pub use self::bounding_box::BoundingBox;
 pub use self::bounding_sphere::BoundingSphere;
 pub use self::bounding_frustum::BoundingFrustum;
+pub use self::oriented_bounding_box::OrientedBoundingBox;
+pub use self::capsule::Capsule;
+pub use self::cylinder::Cylinder;
+pub use self::triangle::Triangle;
+pub use self::polygon::Polygon;
+pub use self::color::{Color, PackedColor};
+pub use self::viewport::Viewport;
+pub use self::layout::{Std140Vector2, Std140Vector2Element, Std140Vector3, Std140Matrix, Std430Vector2, Std430Vector3, Std430Matrix};
@@ -0,0 +1,418 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::bounding_frustum::BoundingFrustum;
+
+// octree over values with bounding boxes. each node's loose bounds are its
+// cell scaled by looseness about the center, so objects near a cell border
+// stay in the child instead of being pushed up to the parent. a looseness of
+// 1 gives a regular octree and 2 the classic loose octree.
+//
+// objects that do not fit the root bounds are kept at the root. nodes split
+// only when an object fits a child, and children that empty out are collapsed
+// back into their parent, their block of 8 reused by the next split.
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Handle {
+    index:      usize,
+    generation: u32
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hit {
+    pub handle:   Handle,
+    pub distance: f32
+}
+
+struct Entry<T> {
+    bounds: BoundingBox,
+    value:  T,
+    node:   usize
+}
+
+struct Slot<T> {
+    generation: u32,
+    entry:      Option<Entry<T>>
+}
+
+struct Node {
+    bounds:   BoundingBox,
+    loose:    BoundingBox,
+    depth:    usize,
+    parent:   Option<usize>,
+    children: Option<usize>,
+    items:    Vec<usize>
+}
+
+pub struct Octree<T> {
+    nodes:      Vec<Node>,
+    slots:      Vec<Slot<T>>,
+    free:       Vec<usize>,
+    free_nodes: Vec<usize>,
+    max_depth:  usize,
+    looseness:  f32,
+    len:        usize
+}
+
+impl<T> Octree<T> {
+
+    pub fn new(bounds: BoundingBox, max_depth: usize, looseness: f32) -> Octree<T> {
+        let looseness = looseness.max(1f32);
+        Octree {
+            nodes:      vec![Node {
+                bounds,
+                loose:    loosen(bounds, looseness),
+                depth:    0,
+                parent:   None,
+                children: None,
+                items:    Vec::new()
+            }],
+            slots:      Vec::new(),
+            free:       Vec::new(),
+            free_nodes: Vec::new(),
+            max_depth,
+            looseness,
+            len:        0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn bounds(&self) -> BoundingBox {
+        self.nodes[0].bounds
+    }
+
+    pub fn insert(&mut self, bounds: BoundingBox, value: T) -> Handle {
+        let node  = self.find_node(bounds);
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None        => {
+                self.slots.push(Slot { generation: 0, entry: None });
+                self.slots.len() - 1
+            }
+        };
+        self.slots[index].entry = Some(Entry { bounds, value, node });
+        self.nodes[node].items.push(index);
+        self.len += 1;
+        Handle { index, generation: self.slots[index].generation }
+    }
+
+    pub fn insert_point(&mut self, point: Vector3, value: T) -> Handle {
+        self.insert(BoundingBox::new(point, point), value)
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        self.entry(handle)?;
+        let slot  = &mut self.slots[handle.index];
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        self.detach(entry.node, handle.index);
+        self.collapse(entry.node);
+        self.len -= 1;
+        Some(entry.value)
+    }
+
+    // moves an object. it is only relocated once it leaves the loose bounds of
+    // its node, which keeps small movements cheap.
+    pub fn update(&mut self, handle: Handle, bounds: BoundingBox) -> bool {
+        let node = match self.entry(handle) {
+            Some(entry) => entry.node,
+            None        => return false
+        };
+        let fits = node != 0 && self.nodes[node].loose.contains_box(bounds);
+        let target = if fits { node } else { self.find_node(bounds) };
+        if target != node {
+            self.detach(node, handle.index);
+            self.nodes[target].items.push(handle.index);
+            self.collapse(node);
+        }
+        if let Some(entry) = self.slots[handle.index].entry.as_mut() {
+            entry.bounds = bounds;
+            entry.node   = target;
+        }
+        true
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.entry(handle).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        match self.slots.get_mut(handle.index) {
+            Some(slot) if slot.generation == handle.generation => slot.entry.as_mut().map(|entry| &mut entry.value),
+            _ => None
+        }
+    }
+
+    pub fn get_bounds(&self, handle: Handle) -> Option<BoundingBox> {
+        self.entry(handle).map(|entry| entry.bounds)
+    }
+
+    pub fn query_point(&self, point: Vector3) -> Vec<Handle> {
+        self.query(|bounds| bounds.contains_point(point))
+    }
+
+    pub fn query_sphere(&self, sphere: BoundingSphere) -> Vec<Handle> {
+        self.query(|bounds| bounds.intersects_sphere(sphere))
+    }
+
+    pub fn query_box(&self, bounds: BoundingBox) -> Vec<Handle> {
+        self.query(|other| other.intersects_box(bounds))
+    }
+
+    pub fn query_frustum(&self, frustum: &BoundingFrustum) -> Vec<Handle> {
+        self.query(|bounds| frustum.intersects_box(bounds))
+    }
+
+    // objects whose bounds the ray hits, nearest first. distance is in units
+    // of direction.
+    pub fn raycast(&self, position: Vector3, direction: Vector3, max_distance: f32) -> Vec<Hit> {
+        let hits_within = |bounds: BoundingBox| match bounds.intersects_ray(position, direction) {
+            Some(distance) if distance <= max_distance => Some(distance),
+            _ => None
+        };
+        let mut hits: Vec<Hit> = self.query(|bounds| hits_within(bounds).is_some()).into_iter().filter_map(|handle| {
+            let bounds = self.get_bounds(handle)?;
+            hits_within(bounds).map(|distance| Hit { handle, distance })
+        }).collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    fn entry(&self, handle: Handle) -> Option<&Entry<T>> {
+        match self.slots.get(handle.index) {
+            Some(slot) if slot.generation == handle.generation => slot.entry.as_ref(),
+            _ => None
+        }
+    }
+
+    fn query<F: Fn(BoundingBox) -> bool>(&self, overlaps: F) -> Vec<Handle> {
+        let mut result = Vec::new();
+        let mut stack  = vec![0usize];
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i];
+            // the root also holds objects outside its bounds.
+            if i != 0 && !overlaps(node.loose) {
+                continue;
+            }
+            for &index in &node.items {
+                let slot = &self.slots[index];
+                if let Some(entry) = slot.entry.as_ref() {
+                    if overlaps(entry.bounds) {
+                        result.push(Handle { index, generation: slot.generation });
+                    }
+                }
+            }
+            if let Some(first) = node.children {
+                stack.extend(first..first + 8);
+            }
+        }
+        result
+    }
+
+    fn detach(&mut self, node: usize, index: usize) {
+        let items = &mut self.nodes[node].items;
+        if let Some(position) = items.iter().position(|&i| i == index) {
+            items.swap_remove(position);
+        }
+    }
+
+    // deepest node whose loose bounds contain the object. a node is only split
+    // once the object is known to fit the child it would go in.
+    fn find_node(&mut self, bounds: BoundingBox) -> usize {
+        let center = bounds.center();
+        let mut node = 0;
+        while self.nodes[node].depth < self.max_depth {
+            let cell  = self.nodes[node].bounds;
+            let index = octant(center, cell.center());
+            if !loosen(child_cell(cell, index), self.looseness).contains_box(bounds) {
+                break;
+            }
+            let first = match self.nodes[node].children {
+                Some(first) => first,
+                None        => self.split(node)
+            };
+            node = first + index;
+        }
+        node
+    }
+
+    fn split(&mut self, node: usize) -> usize {
+        let first  = self.free_nodes.pop().unwrap_or(self.nodes.len());
+        let bounds = self.nodes[node].bounds;
+        let depth  = self.nodes[node].depth + 1;
+        for i in 0..8 {
+            let cell  = child_cell(bounds, i);
+            let child = Node {
+                bounds:   cell,
+                loose:    loosen(cell, self.looseness),
+                depth,
+                parent:   Some(node),
+                children: None,
+                items:    Vec::new()
+            };
+            if first + i < self.nodes.len() {
+                self.nodes[first + i] = child;
+            } else {
+                self.nodes.push(child);
+            }
+        }
+        self.nodes[node].children = Some(first);
+        first
+    }
+
+    // frees the children of each ancestor whose children are all empty leaves.
+    fn collapse(&mut self, mut node: usize) {
+        while let Some(parent) = self.nodes[node].parent {
+            let first = match self.nodes[parent].children {
+                Some(first) => first,
+                None        => break
+            };
+            if (first..first + 8).any(|i| !self.nodes[i].items.is_empty() || self.nodes[i].children.is_some()) {
+                break;
+            }
+            self.nodes[parent].children = None;
+            self.free_nodes.push(first);
+            node = parent;
+        }
+    }
+}
+
+fn child_cell(bounds: BoundingBox, octant: usize) -> BoundingBox {
+    let center = bounds.center();
+    let min = Vector3::new(
+        if octant & 1 == 0 { bounds.min.x } else { center.x },
+        if octant & 2 == 0 { bounds.min.y } else { center.y },
+        if octant & 4 == 0 { bounds.min.z } else { center.z });
+    let max = Vector3::new(
+        if octant & 1 == 0 { center.x } else { bounds.max.x },
+        if octant & 2 == 0 { center.y } else { bounds.max.y },
+        if octant & 4 == 0 { center.z } else { bounds.max.z });
+    BoundingBox::new(min, max)
+}
+
+fn loosen(bounds: BoundingBox, looseness: f32) -> BoundingBox {
+    let center  = bounds.center();
+    let extents = bounds.extents() * looseness;
+    BoundingBox::new(center - extents, center + extents)
+}
+
+fn octant(point: Vector3, center: Vector3) -> usize {
+    (if point.x >= center.x { 1 } else { 0 }) |
+    (if point.y >= center.y { 2 } else { 0 }) |
+    (if point.z >= center.z { 4 } else { 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, BoundingBox, BoundingSphere};
+    use super::Octree;
+
+    fn cube(center: Vector3, extent: f32) -> BoundingBox {
+        BoundingBox::new(center - Vector3::new(extent, extent, extent), center + Vector3::new(extent, extent, extent))
+    }
+
+    fn world() -> Octree<usize> {
+        Octree::new(cube(Vector3::zero(), 64f32), 6, 1.5f32)
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let mut tree = world();
+        let mut boxes = Vec::new();
+        for i in 0..500 {
+            let t = i as f32;
+            let center = Vector3::new((t * 7.3f32) % 120f32 - 60f32, (t * 13.1f32) % 120f32 - 60f32, (t * 3.7f32) % 120f32 - 60f32);
+            let bounds = cube(center, 0.5f32 + (i % 7) as f32);
+            boxes.push((tree.insert(bounds, i), bounds));
+        }
+        assert_eq!(tree.len(), 500);
+        let region = cube(Vector3::new(10f32, -5f32, 3f32), 20f32);
+        let mut found: Vec<usize> = tree.query_box(region).into_iter().map(|h| *tree.get(h).unwrap()).collect();
+        found.sort();
+        let expected: Vec<usize> = (0..500).filter(|&i| boxes[i].1.intersects_box(region)).collect();
+        assert_eq!(found, expected);
+
+        let sphere = BoundingSphere::new(Vector3::new(-20f32, 10f32, 0f32), 15f32);
+        assert_eq!(tree.query_sphere(sphere).len(), boxes.iter().filter(|b| b.1.intersects_sphere(sphere)).count());
+
+        let hits = tree.raycast(Vector3::new(-100f32, 0f32, 0f32), Vector3::UNIT_X, 200f32);
+        assert_eq!(hits.len(), boxes.iter().filter(|b| b.1.intersects_ray(Vector3::new(-100f32, 0f32, 0f32), Vector3::UNIT_X).is_some()).count());
+        assert!(hits.windows(2).all(|w| w[0].distance <= w[1].distance));
+    }
+
+    #[test]
+    fn large_objects_do_not_split() {
+        let mut tree = world();
+        tree.insert(cube(Vector3::new(1f32, 1f32, 1f32), 40f32), 0);
+        assert_eq!(tree.nodes.len(), 1);
+        tree.insert_point(Vector3::new(1f32, 1f32, 1f32), 1);
+        assert_eq!(tree.nodes.len(), 1 + (8 * 6));
+    }
+
+    #[test]
+    fn removed_nodes_are_reused() {
+        let mut tree = world();
+        for round in 0..10 {
+            let handles: Vec<_> = (0..50).map(|i| tree.insert_point(Vector3::new(i as f32 - 25f32, round as f32, 0f32), i)).collect();
+            for handle in handles {
+                assert!(tree.remove(handle).is_some());
+                assert!(tree.get(handle).is_none());
+                assert!(tree.remove(handle).is_none());
+            }
+            assert!(tree.is_empty());
+            assert!(tree.nodes[0].children.is_none());
+        }
+        assert!(tree.nodes.len() < 50 * 8 * 6);
+        assert_eq!(tree.free_nodes.len() * 8 + 1, tree.nodes.len());
+    }
+
+    #[test]
+    fn update_relocates_and_collapses() {
+        let mut tree = world();
+        let handle = tree.insert_point(Vector3::new(30f32, 30f32, 30f32), 7);
+        assert!(tree.update(handle, cube(Vector3::new(30.1f32, 30f32, 30f32), 0.1f32)));
+        assert!(tree.update(handle, cube(Vector3::new(-30f32, -30f32, -30f32), 0.1f32)));
+        assert!(tree.query_point(Vector3::new(30f32, 30f32, 30f32)).is_empty());
+        assert_eq!(tree.query_point(Vector3::new(-30f32, -30f32, -30f32)), vec![handle]);
+        let in_use = tree.nodes.len() - (tree.free_nodes.len() * 8);
+        assert_eq!(in_use, 1 + (8 * 6));
+        assert!(tree.remove(handle).is_some());
+        assert!(!tree.update(handle, cube(Vector3::zero(), 1f32)));
+    }
+}
@@ -0,0 +1,412 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::matrix::Matrix;
+use super::quaternion::Quaternion;
+use super::bounding_box::BoundingBox;
+use super::query;
+use super::scalar::EPSILON;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct OrientedBoundingBox {
+    pub center:       Vector3,
+    pub half_extents: Vector3,
+    pub axes:         [Vector3; 3]
+}
+
+impl OrientedBoundingBox {
+
+    pub fn new(center: Vector3, half_extents: Vector3, axes: [Vector3; 3]) -> OrientedBoundingBox {
+        OrientedBoundingBox {
+            center,
+            half_extents,
+            axes
+        }
+    }
+
+    pub fn from_quaternion(center: Vector3, half_extents: Vector3, rotation: Quaternion) -> OrientedBoundingBox {
+        OrientedBoundingBox {
+            center,
+            half_extents,
+            axes: [
+                Vector3::transform_quaternion(Vector3::new(1f32, 0f32, 0f32), rotation),
+                Vector3::transform_quaternion(Vector3::new(0f32, 1f32, 0f32), rotation),
+                Vector3::transform_quaternion(Vector3::new(0f32, 0f32, 1f32), rotation)
+            ]
+        }
+    }
+
+    pub fn from_box(bounds: BoundingBox, matrix: Matrix) -> OrientedBoundingBox {
+        let extents = bounds.extents();
+        let x = matrix.right();
+        let y = matrix.up();
+        let z = matrix.backward();
+        OrientedBoundingBox {
+            center: Vector3::transform(bounds.center(), matrix),
+            half_extents: Vector3::new(extents.x * x.length(),
+                                       extents.y * y.length(),
+                                       extents.z * z.length()),
+            axes: [x.normalize(), y.normalize(), z.normalize()]
+        }
+    }
+
+    pub fn from_points(points: &[Vector3]) -> OrientedBoundingBox {
+        if points.is_empty() {
+            return OrientedBoundingBox::from_box(BoundingBox::new(Vector3::zero(), Vector3::zero()), Matrix::identity());
+        }
+        let scale = 1f32 / points.len() as f32;
+        let mut mean = Vector3::zero();
+        for point in points {
+            mean += *point;
+        }
+        mean *= scale;
+        let mut covariance = [[0f32; 3]; 3];
+        for point in points {
+            let d = [point.x - mean.x, point.y - mean.y, point.z - mean.z];
+            for i in 0..3 {
+                for j in 0..3 {
+                    covariance[i][j] += d[i] * d[j] * scale;
+                }
+            }
+        }
+        let eigen = eigen_vectors(covariance);
+        let axes  = [eigen[0], eigen[1], Vector3::cross(eigen[0], eigen[1])];
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for point in points {
+            for i in 0..3 {
+                let distance = Vector3::dot(*point - mean, axes[i]);
+                min[i] = min[i].min(distance);
+                max[i] = max[i].max(distance);
+            }
+        }
+        let mut center = mean;
+        for i in 0..3 {
+            center += axes[i] * ((min[i] + max[i]) * 0.5f32);
+        }
+        OrientedBoundingBox {
+            center,
+            half_extents: Vector3::new((max[0] - min[0]) * 0.5f32,
+                                       (max[1] - min[1]) * 0.5f32,
+                                       (max[2] - min[2]) * 0.5f32),
+            axes
+        }
+    }
+
+    pub fn extents(&self) -> [f32; 3] {
+        [self.half_extents.x, self.half_extents.y, self.half_extents.z]
+    }
+
+    pub fn corners(&self) -> [Vector3; 8] {
+        let x = self.axes[0] * self.half_extents.x;
+        let y = self.axes[1] * self.half_extents.y;
+        let z = self.axes[2] * self.half_extents.z;
+        let c = self.center;
+        [
+            ((c - x) + y) + z, ((c + x) + y) + z, ((c + x) - y) + z, ((c - x) - y) + z,
+            ((c - x) + y) - z, ((c + x) + y) - z, ((c + x) - y) - z, ((c - x) - y) - z
+        ]
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        query::closest_point_on_oriented_box(point, self.center, self.axes, self.half_extents)
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        let d       = point - self.center;
+        let extents = self.extents();
+        for (axis, extent) in self.axes.iter().zip(extents.iter()) {
+            if Vector3::dot(d, *axis).abs() > *extent {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn contains_box(&self, bounds: BoundingBox) -> bool {
+        self.contains_oriented_box(OrientedBoundingBox::from_box(bounds, Matrix::identity()))
+    }
+
+    pub fn contains_oriented_box(&self, other: OrientedBoundingBox) -> bool {
+        other.corners().iter().all(|corner| self.contains_point(*corner))
+    }
+
+    pub fn intersects_box(&self, bounds: BoundingBox) -> bool {
+        self.intersects_oriented_box(OrientedBoundingBox::from_box(bounds, Matrix::identity()))
+    }
+
+    pub fn intersects_oriented_box(&self, other: OrientedBoundingBox) -> bool {
+        let a = self.extents();
+        let b = other.extents();
+        let mut r     = [[0f32; 3]; 3];
+        let mut abs_r = [[0f32; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                r[i][j]     = Vector3::dot(self.axes[i], other.axes[j]);
+                abs_r[i][j] = r[i][j].abs() + EPSILON;
+            }
+        }
+        let d = other.center - self.center;
+        let t = [Vector3::dot(d, self.axes[0]),
+                 Vector3::dot(d, self.axes[1]),
+                 Vector3::dot(d, self.axes[2])];
+        for i in 0..3 {
+            let ra = a[i];
+            let rb = ((b[0] * abs_r[i][0]) + (b[1] * abs_r[i][1])) + (b[2] * abs_r[i][2]);
+            if t[i].abs() > ra + rb {
+                return false;
+            }
+        }
+        for j in 0..3 {
+            let ra = ((a[0] * abs_r[0][j]) + (a[1] * abs_r[1][j])) + (a[2] * abs_r[2][j]);
+            let rb = b[j];
+            if (((t[0] * r[0][j]) + (t[1] * r[1][j])) + (t[2] * r[2][j])).abs() > ra + rb {
+                return false;
+            }
+        }
+        for i in 0..3 {
+            let i1 = (i + 1) % 3;
+            let i2 = (i + 2) % 3;
+            for j in 0..3 {
+                let j1 = (j + 1) % 3;
+                let j2 = (j + 2) % 3;
+                let ra = (a[i1] * abs_r[i2][j]) + (a[i2] * abs_r[i1][j]);
+                let rb = (b[j1] * abs_r[i][j2]) + (b[j2] * abs_r[i][j1]);
+                if ((t[i2] * r[i1][j]) - (t[i1] * r[i2][j])).abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn intersects_triangle(&self, a: Vector3, b: Vector3, c: Vector3) -> bool {
+        let e = self.half_extents;
+        let local = |point: Vector3| {
+            let d = point - self.center;
+            Vector3::new(Vector3::dot(d, self.axes[0]),
+                         Vector3::dot(d, self.axes[1]),
+                         Vector3::dot(d, self.axes[2]))
+        };
+        let v = [local(a), local(b), local(c)];
+        let separated = |axis: Vector3| {
+            let p0 = Vector3::dot(v[0], axis);
+            let p1 = Vector3::dot(v[1], axis);
+            let p2 = Vector3::dot(v[2], axis);
+            let r  = ((e.x * axis.x.abs()) + (e.y * axis.y.abs())) + (e.z * axis.z.abs());
+            p0.min(p1).min(p2) > r || p0.max(p1).max(p2) < -r
+        };
+        let units = [Vector3::new(1f32, 0f32, 0f32),
+                     Vector3::new(0f32, 1f32, 0f32),
+                     Vector3::new(0f32, 0f32, 1f32)];
+        let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+        for unit in units.iter() {
+            if separated(*unit) {
+                return false;
+            }
+        }
+        if separated(Vector3::cross(edges[0], edges[1])) {
+            return false;
+        }
+        for unit in units.iter() {
+            for edge in edges.iter() {
+                if separated(Vector3::cross(*unit, *edge)) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        let d       = position - self.center;
+        let extents = self.extents();
+        let mut min = 0f32;
+        let mut max = f32::MAX;
+        for (axis, extent) in self.axes.iter().zip(extents.iter()) {
+            let origin = Vector3::dot(d, *axis);
+            let delta  = Vector3::dot(direction, *axis);
+            if delta.abs() < EPSILON {
+                if origin < -extent || origin > *extent {
+                    return None;
+                }
+            } else {
+                let inverse = 1f32 / delta;
+                let mut t1 = (-extent - origin) * inverse;
+                let mut t2 = ( extent - origin) * inverse;
+                if t1 > t2 {
+                    std::mem::swap(&mut t1, &mut t2);
+                }
+                min = min.max(t1);
+                max = max.min(t2);
+                if min > max {
+                    return None;
+                }
+            }
+        }
+        Some(min)
+    }
+}
+
+fn eigen_vectors(covariance: [[f32; 3]; 3]) -> [Vector3; 3] {
+    let mut a = covariance;
+    let mut v = [[1f32, 0f32, 0f32], [0f32, 1f32, 0f32], [0f32, 0f32, 1f32]];
+    for _ in 0..50 {
+        let mut p = 0;
+        let mut q = 1;
+        if a[0][2].abs() > a[p][q].abs() { p = 0; q = 2; }
+        if a[1][2].abs() > a[p][q].abs() { p = 1; q = 2; }
+        if a[p][q].abs() < 1e-9f32 {
+            break;
+        }
+        let theta = (a[q][q] - a[p][p]) / (2f32 * a[p][q]);
+        let t = theta.signum() / (theta.abs() + ((theta * theta) + 1f32).sqrt());
+        let c = 1f32 / ((t * t) + 1f32).sqrt();
+        let s = t * c;
+        let mut j = [[1f32, 0f32, 0f32], [0f32, 1f32, 0f32], [0f32, 0f32, 1f32]];
+        j[p][p] =  c;
+        j[q][q] =  c;
+        j[p][q] =  s;
+        j[q][p] = -s;
+        let mut aj = [[0f32; 3]; 3];
+        let mut vj = [[0f32; 3]; 3];
+        for row in 0..3 {
+            for col in 0..3 {
+                for k in 0..3 {
+                    aj[row][col] += a[row][k] * j[k][col];
+                    vj[row][col] += v[row][k] * j[k][col];
+                }
+            }
+        }
+        let mut next = [[0f32; 3]; 3];
+        for row in 0..3 {
+            for col in 0..3 {
+                for k in 0..3 {
+                    next[row][col] += j[k][row] * aj[k][col];
+                }
+            }
+        }
+        a = next;
+        v = vj;
+    }
+    [Vector3::new(v[0][0], v[1][0], v[2][0]),
+     Vector3::new(v[0][1], v[1][1], v[2][1]),
+     Vector3::new(v[0][2], v[1][2], v[2][2])]
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Quaternion, Matrix, BoundingBox, Degrees};
+    use super::OrientedBoundingBox;
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-4f32
+    }
+
+    fn unit() -> OrientedBoundingBox {
+        OrientedBoundingBox::from_box(BoundingBox::new(-Vector3::ONE, Vector3::ONE), Matrix::identity())
+    }
+
+    fn diamond(center: Vector3) -> OrientedBoundingBox {
+        OrientedBoundingBox::from_quaternion(center, Vector3::ONE, Quaternion::from_axis_angle(Vector3::UNIT_Z, Degrees(45f32)))
+    }
+
+    #[test]
+    fn rotated_box_against_axis_aligned_box() {
+        let reach = 1f32 + 2f32.sqrt();
+        assert!(unit().intersects_oriented_box(diamond(Vector3::new(reach - 0.05f32, 0f32, 0f32))));
+        assert!(!unit().intersects_oriented_box(diamond(Vector3::new(reach + 0.05f32, 0f32, 0f32))));
+        assert!(diamond(Vector3::new(1.6f32, 1.6f32, 0f32)).intersects_box(BoundingBox::new(-Vector3::ONE, Vector3::ONE)));
+        assert!(!diamond(Vector3::new(2.3f32, 2.3f32, 0f32)).intersects_box(BoundingBox::new(-Vector3::ONE, Vector3::ONE)));
+        assert!(!diamond(Vector3::new(0f32, 0f32, 2.05f32)).intersects_oriented_box(unit()));
+    }
+
+    #[test]
+    fn triangle_against_box() {
+        let obb = diamond(Vector3::zero());
+        assert!(obb.intersects_triangle(Vector3::new(-5f32, 0f32, 0f32), Vector3::new(5f32, 0f32, 0f32), Vector3::new(0f32, 0f32, 5f32)));
+        assert!(!obb.intersects_triangle(Vector3::new(2f32, 0f32, 0f32), Vector3::new(3f32, 0f32, 0f32), Vector3::new(2f32, 1f32, 0f32)));
+        // separated only by the triangle normal, across the corner at (1, 1, 1).
+        let plane = |offset: f32| {
+            let o = offset / 3f32;
+            [Vector3::new(o + 6f32, o - 3f32, o - 3f32), Vector3::new(o - 3f32, o + 6f32, o - 3f32), Vector3::new(o - 3f32, o - 3f32, o + 6f32)]
+        };
+        let [a, b, c] = plane(3.1f32);
+        assert!(!unit().intersects_triangle(a, b, c));
+        let [a, b, c] = plane(2.9f32);
+        assert!(unit().intersects_triangle(a, b, c));
+    }
+
+    #[test]
+    fn ray_against_rotated_box() {
+        let obb = diamond(Vector3::zero());
+        let t = obb.intersects_ray(Vector3::new(-5f32, 0f32, 0f32), Vector3::UNIT_X).unwrap();
+        assert!((t - (5f32 - 2f32.sqrt())).abs() < 1e-4f32);
+        assert_eq!(obb.intersects_ray(Vector3::new(0.5f32, 0f32, 0f32), Vector3::UNIT_X), Some(0f32));
+        assert!(obb.intersects_ray(Vector3::new(-5f32, 3f32, 0f32), Vector3::UNIT_X).is_none());
+        assert!(obb.intersects_ray(Vector3::new(-5f32, 0f32, 0f32), -Vector3::UNIT_X).is_none());
+        assert!(unit().intersects_ray(Vector3::new(-5f32, 0f32, 1.5f32), Vector3::UNIT_X).is_none());
+    }
+
+    #[test]
+    fn contains_points_and_boxes() {
+        let obb = diamond(Vector3::zero());
+        assert!(obb.contains_point(Vector3::new(1.3f32, 0f32, 0f32)));
+        assert!(!obb.contains_point(Vector3::new(1.5f32, 0f32, 0f32)));
+        assert!(!obb.contains_point(Vector3::new(0.9f32, 0.9f32, 0f32)));
+        assert!(obb.contains_box(BoundingBox::new(Vector3::new(-0.5f32, -0.5f32, -0.5f32), Vector3::new(0.5f32, 0.5f32, 0.5f32))));
+        assert!(!obb.contains_box(BoundingBox::new(Vector3::new(-0.8f32, -0.8f32, -0.5f32), Vector3::new(0.8f32, 0.8f32, 0.5f32))));
+        assert!(near(obb.closest_point(Vector3::new(3f32, 0f32, 0f32)), Vector3::new(2f32.sqrt(), 0f32, 0f32)));
+    }
+
+    #[test]
+    fn from_box_applies_the_matrix() {
+        let matrix = Matrix::scale(Vector3::new(2f32, 1f32, 1f32)) * Matrix::translation(Vector3::UNIT_X);
+        let obb = OrientedBoundingBox::from_box(BoundingBox::new(Vector3::zero(), Vector3::new(2f32, 2f32, 2f32)), matrix);
+        assert!(near(obb.center, Vector3::new(3f32, 1f32, 1f32)));
+        assert!(near(obb.half_extents, Vector3::new(2f32, 1f32, 1f32)));
+        assert!(near(obb.axes[0], Vector3::UNIT_X));
+    }
+
+    #[test]
+    fn from_points_recovers_a_rotated_box() {
+        let rotation = Quaternion::from_yaw_pitch_roll(Degrees(30f32), Degrees(20f32), Degrees(10f32));
+        let source   = OrientedBoundingBox::from_quaternion(Vector3::new(1f32, 2f32, 3f32), Vector3::new(3f32, 2f32, 1f32), rotation);
+        let fitted   = OrientedBoundingBox::from_points(&source.corners());
+        assert!(near(fitted.center, source.center));
+        let extents = fitted.extents();
+        for (axis, extent) in source.axes.iter().zip(source.extents().iter()) {
+            let matched = fitted.axes.iter().zip(extents.iter())
+                .any(|(fit, fit_extent)| Vector3::dot(*axis, *fit).abs() > 0.9999f32 && (fit_extent - extent).abs() < 1e-3f32);
+            assert!(matched);
+        }
+        assert!(source.corners().iter().all(|corner| fitted.contains_point(*corner + ((fitted.center - *corner) * 1e-4f32))));
+    }
+}
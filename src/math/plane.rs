@@ -34,12 +34,14 @@ use super::vector4::Vector4;
 use super::quaternion::Quaternion;
 use super::matrix::Matrix;
 
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub enum IntersectionType {
     front, 
     back,
     intersecting    
 }
 
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Plane {
     pub a: f32,
@@ -50,12 +52,12 @@ pub struct Plane {
 
 impl Plane {
     
-    pub fn new(a: f32, b: f32, c: f32, d: f32) -> Plane {
-        Plane{
-            a: a, 
-            b: b,
-            c: c,
-            d: d
+    pub const fn new(a: f32, b: f32, c: f32, d: f32) -> Plane {
+        Plane {
+            a,
+            b,
+            c,
+            d
         }
     }
 
@@ -0,0 +1,168 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::triangle::Triangle;
+
+#[derive(Clone)]
+pub struct Polygon {
+    pub vertices: Vec<Vector3>
+}
+
+impl Polygon {
+
+    pub fn new(vertices: Vec<Vector3>) -> Polygon {
+        Polygon {
+            vertices
+        }
+    }
+
+    // newell's method. the length of the unnormalized normal is twice the area.
+    fn newell(&self) -> Vector3 {
+        let mut normal = Vector3::zero();
+        let count = self.vertices.len();
+        for i in 0..count {
+            let current = self.vertices[i];
+            let next    = self.vertices[(i + 1) % count];
+            normal.x += (current.y - next.y) * (current.z + next.z);
+            normal.y += (current.z - next.z) * (current.x + next.x);
+            normal.z += (current.x - next.x) * (current.y + next.y);
+        }
+        normal
+    }
+
+    pub fn normal(&self) -> Vector3 {
+        self.newell().normalize()
+    }
+
+    pub fn area(&self) -> f32 {
+        self.newell().length() * 0.5f32
+    }
+
+    // positive when the winding is counter-clockwise about the reference normal.
+    pub fn signed_area(&self, reference: Vector3) -> f32 {
+        Vector3::dot(self.newell(), reference.normalize()) * 0.5f32
+    }
+
+    pub fn is_convex(&self) -> bool {
+        let count = self.vertices.len();
+        if count < 3 {
+            return false;
+        }
+        let normal = self.newell();
+        for i in 0..count {
+            let previous = self.vertices[(i + count - 1) % count];
+            let current  = self.vertices[i];
+            let next     = self.vertices[(i + 1) % count];
+            let turn     = Vector3::cross(current - previous, next - current);
+            if Vector3::dot(turn, normal) < 0f32 {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let mut triangles = Vec::new();
+        if self.vertices.len() < 3 {
+            return triangles;
+        }
+        let normal = self.newell();
+        let mut indices: Vec<usize> = (0..self.vertices.len()).collect();
+        let mut guard = indices.len() * indices.len();
+        let mut i = 0;
+        while indices.len() > 3 && guard > 0 {
+            guard -= 1;
+            let count    = indices.len();
+            let previous = indices[(i + count - 1) % count];
+            let current  = indices[i % count];
+            let next     = indices[(i + 1) % count];
+            if self.is_ear(previous, current, next, &indices, normal) {
+                triangles.push([previous, current, next]);
+                indices.remove(i % count);
+            } else {
+                i += 1;
+            }
+            i %= indices.len();
+        }
+        // degenerate or self intersecting input can leave no ears before the
+        // guard runs out. the rest is fanned so there are always n - 2 triangles.
+        for k in 1..(indices.len() - 1) {
+            triangles.push([indices[0], indices[k], indices[k + 1]]);
+        }
+        triangles
+    }
+
+    fn is_ear(&self, previous: usize, current: usize, next: usize, indices: &[usize], normal: Vector3) -> bool {
+        let a = self.vertices[previous];
+        let b = self.vertices[current];
+        let c = self.vertices[next];
+        if Vector3::dot(Vector3::cross(b - a, c - b), normal) <= 0f32 {
+            return false;
+        }
+        let triangle = Triangle::new(a, b, c);
+        indices.iter()
+               .filter(|index| **index != previous && **index != current && **index != next)
+               .all(|index| !triangle.projection_contains_point(self.vertices[*index]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Triangle};
+    use super::Polygon;
+
+    fn area(polygon: &Polygon, triangles: &[[usize; 3]]) -> f32 {
+        triangles.iter().map(|t| {
+            let triangle = Triangle::new(polygon.vertices[t[0]], polygon.vertices[t[1]], polygon.vertices[t[2]]);
+            Vector3::cross(triangle.b - triangle.a, triangle.c - triangle.a).length() * 0.5f32
+        }).sum()
+    }
+
+    #[test]
+    fn triangulates_a_concave_polygon() {
+        let polygon = Polygon::new(vec![
+            Vector3::new(0f32, 0f32, 0f32), Vector3::new(4f32, 0f32, 0f32), Vector3::new(4f32, 1f32, 0f32),
+            Vector3::new(1f32, 1f32, 0f32), Vector3::new(1f32, 3f32, 0f32), Vector3::new(0f32, 3f32, 0f32)]);
+        assert!(!polygon.is_convex());
+        let triangles = polygon.triangulate();
+        assert_eq!(triangles.len(), 4);
+        assert!((area(&polygon, &triangles) - polygon.area()).abs() < 1e-5f32);
+    }
+
+    #[test]
+    fn degenerate_polygons_still_give_n_minus_2_triangles() {
+        let collinear = Polygon::new((0..6).map(|i| Vector3::new(i as f32, 0f32, 0f32)).collect());
+        assert_eq!(collinear.triangulate().len(), 4);
+        let bowtie = Polygon::new(vec![
+            Vector3::new(0f32, 0f32, 0f32), Vector3::new(2f32, 2f32, 0f32),
+            Vector3::new(2f32, 0f32, 0f32), Vector3::new(0f32, 2f32, 0f32)]);
+        assert_eq!(bowtie.triangulate().len(), 2);
+        assert!(Polygon::new(vec![Vector3::zero(), Vector3::UNIT_X]).triangulate().is_empty());
+    }
+}
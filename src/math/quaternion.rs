@@ -26,6 +26,11 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
+use std::ops::{Add, Sub, Mul, Neg};
+use super::vector3::Vector3;
+use super::angle::Radians;
+
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Quaternion {
     pub x: f32,
@@ -33,3 +38,170 @@ pub struct Quaternion {
     pub z: f32,
     pub w: f32
 }
+
+impl Add<Quaternion> for Quaternion {
+    type Output = Quaternion;
+    fn add(self, rhs: Quaternion) -> Quaternion {
+        Quaternion {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w
+        }
+    }
+}
+
+impl Sub<Quaternion> for Quaternion {
+    type Output = Quaternion;
+    fn sub(self, rhs: Quaternion) -> Quaternion {
+        Quaternion {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w
+        }
+    }
+}
+
+impl Mul<Quaternion> for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, rhs: Quaternion) -> Quaternion {
+        let num12 = (self.y * rhs.z) - (self.z * rhs.y);
+        let num11 = (self.z * rhs.x) - (self.x * rhs.z);
+        let num10 = (self.x * rhs.y) - (self.y * rhs.x);
+        let num9  = ((self.x * rhs.x) + (self.y * rhs.y)) + (self.z * rhs.z);
+        Quaternion {
+            x: ((self.x * rhs.w) + (rhs.x * self.w)) + num12,
+            y: ((self.y * rhs.w) + (rhs.y * self.w)) + num11,
+            z: ((self.z * rhs.w) + (rhs.z * self.w)) + num10,
+            w: (self.w * rhs.w) - num9
+        }
+    }
+}
+
+impl Mul<f32> for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, rhs: f32) -> Quaternion {
+        Quaternion {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+            w: self.w * rhs
+        }
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Quaternion;
+    fn neg(self) -> Quaternion {
+        Quaternion {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w
+        }
+    }
+}
+
+impl_assign_op!(Quaternion, Quaternion, AddAssign, add_assign, +);
+impl_assign_op!(Quaternion, Quaternion, SubAssign, sub_assign, -);
+impl_assign_op!(Quaternion, Quaternion, MulAssign, mul_assign, *);
+impl_assign_op!(Quaternion, f32,        MulAssign, mul_assign, *);
+impl_ref_binop!(Quaternion, Quaternion, Add, add);
+impl_ref_binop!(Quaternion, Quaternion, Sub, sub);
+impl_ref_binop!(Quaternion, Quaternion, Mul, mul);
+impl_ref_binop!(Quaternion, f32,        Mul, mul);
+impl_ref_neg!(Quaternion);
+impl_scalar_mul!(Quaternion);
+impl_product!(Quaternion, Quaternion::IDENTITY);
+impl_component_index!(Quaternion);
+
+impl Quaternion {
+
+    pub const IDENTITY: Quaternion = Quaternion::new(0f32, 0f32, 0f32, 1f32);
+
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Quaternion {
+        Quaternion {
+            x,
+            y,
+            z,
+            w
+        }
+    }
+
+    pub const fn from_array(array: [f32; 4]) -> Quaternion {
+        Quaternion::new(array[0], array[1], array[2], array[3])
+    }
+
+    pub const fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    pub fn as_array(&self) -> &[f32; 4] {
+        unsafe { &*(self as *const Quaternion as *const [f32; 4]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 4] {
+        unsafe { &mut *(self as *mut Quaternion as *mut [f32; 4]) }
+    }
+
+    pub const fn identity() -> Quaternion {
+        Quaternion::IDENTITY
+    }
+
+    pub fn from_axis_angle(axis: Vector3, angle: impl Into<Radians>) -> Quaternion {
+        let half = angle.into().0 * 0.5f32;
+        let sin  = half.sin();
+        Quaternion {
+            x: axis.x * sin,
+            y: axis.y * sin,
+            z: axis.z * sin,
+            w: half.cos()
+        }
+    }
+
+    // xna order: roll about z, then pitch about x, then yaw about y.
+    pub fn from_yaw_pitch_roll(yaw: impl Into<Radians>, pitch: impl Into<Radians>, roll: impl Into<Radians>) -> Quaternion {
+        let (sin_roll,  cos_roll)  = (roll.into().0 * 0.5f32).sin_cos();
+        let (sin_pitch, cos_pitch) = (pitch.into().0 * 0.5f32).sin_cos();
+        let (sin_yaw,   cos_yaw)   = (yaw.into().0 * 0.5f32).sin_cos();
+        Quaternion {
+            x: ((cos_yaw * sin_pitch) * cos_roll) + ((sin_yaw * cos_pitch) * sin_roll),
+            y: ((sin_yaw * cos_pitch) * cos_roll) - ((cos_yaw * sin_pitch) * sin_roll),
+            z: ((cos_yaw * cos_pitch) * sin_roll) - ((sin_yaw * sin_pitch) * cos_roll),
+            w: ((cos_yaw * cos_pitch) * cos_roll) + ((sin_yaw * sin_pitch) * sin_roll)
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        Quaternion::dot(*self, *self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Quaternion {
+        *self * (1f32 / self.length())
+    }
+
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn dot(q1: Quaternion, q2: Quaternion) -> f32 {
+        (((q1.x * q2.x) + (q1.y * q2.y)) + (q1.z * q2.z)) + (q1.w * q2.w)
+    }
+
+    // takes the shorter arc, falling back to normalized lerp when nearly equal.
+    pub fn slerp(q1: Quaternion, q2: Quaternion, amount: f32) -> Quaternion {
+        let mut dot = Quaternion::dot(q1, q2);
+        let q2 = if dot < 0f32 { dot = -dot; -q2 } else { q2 };
+        if dot > 0.9995f32 {
+            return ((q1 * (1f32 - amount)) + (q2 * amount)).normalize();
+        }
+        let angle = dot.acos();
+        let sin   = angle.sin();
+        (q1 * (((1f32 - amount) * angle).sin() / sin)) + (q2 * ((amount * angle).sin() / sin))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,333 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::plane::Plane;
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::scalar::EPSILON;
+
+fn saturate(value: f32) -> f32 {
+    value.clamp(0f32, 1f32)
+}
+
+pub fn closest_point_on_segment(point: Vector3, a: Vector3, b: Vector3) -> Vector3 {
+    let ab    = b - a;
+    let denom = Vector3::dot(ab, ab);
+    if denom <= EPSILON {
+        return a;
+    }
+    let t = saturate(Vector3::dot(point - a, ab) / denom);
+    a + (ab * t)
+}
+
+pub fn closest_point_on_triangle(point: Vector3, a: Vector3, b: Vector3, c: Vector3) -> Vector3 {
+    let ab = b - a;
+    let ac = c - a;
+    // a zero area triangle is a segment or a point, closest on one of its edges.
+    let area = Vector3::cross(ab, ac).length_squared();
+    if area <= (EPSILON * EPSILON) * (ab.length_squared() * ac.length_squared()) {
+        let mut best = closest_point_on_segment(point, a, b);
+        for candidate in [closest_point_on_segment(point, b, c), closest_point_on_segment(point, c, a)] {
+            if (point - candidate).length_squared() < (point - best).length_squared() {
+                best = candidate;
+            }
+        }
+        return best;
+    }
+    let ap = point - a;
+    let d1 = Vector3::dot(ab, ap);
+    let d2 = Vector3::dot(ac, ap);
+    if d1 <= 0f32 && d2 <= 0f32 {
+        return a;
+    }
+    let bp = point - b;
+    let d3 = Vector3::dot(ab, bp);
+    let d4 = Vector3::dot(ac, bp);
+    if d3 >= 0f32 && d4 <= d3 {
+        return b;
+    }
+    let vc = (d1 * d4) - (d3 * d2);
+    if vc <= 0f32 && d1 >= 0f32 && d3 <= 0f32 {
+        let v = d1 / (d1 - d3);
+        return a + (ab * v);
+    }
+    let cp = point - c;
+    let d5 = Vector3::dot(ab, cp);
+    let d6 = Vector3::dot(ac, cp);
+    if d6 >= 0f32 && d5 <= d6 {
+        return c;
+    }
+    let vb = (d5 * d2) - (d1 * d6);
+    if vb <= 0f32 && d2 >= 0f32 && d6 <= 0f32 {
+        let w = d2 / (d2 - d6);
+        return a + (ac * w);
+    }
+    let va = (d3 * d6) - (d5 * d4);
+    if va <= 0f32 && (d4 - d3) >= 0f32 && (d5 - d6) >= 0f32 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return b + ((c - b) * w);
+    }
+    let denom = 1f32 / ((va + vb) + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    (a + (ab * v)) + (ac * w)
+}
+
+pub fn closest_point_on_box(point: Vector3, bounds: BoundingBox) -> Vector3 {
+    Vector3::clamp(point, bounds.min, bounds.max)
+}
+
+pub fn closest_point_on_oriented_box(point: Vector3, center: Vector3, axes: [Vector3; 3], half_extents: Vector3) -> Vector3 {
+    let d       = point - center;
+    let extents = [half_extents.x, half_extents.y, half_extents.z];
+    let mut result = center;
+    for i in 0..3 {
+        let mut distance = Vector3::dot(d, axes[i]);
+        distance = if distance >  extents[i] {  extents[i] } else { distance };
+        distance = if distance < -extents[i] { -extents[i] } else { distance };
+        result += axes[i] * distance;
+    }
+    result
+}
+
+pub fn closest_point_on_sphere(point: Vector3, sphere: BoundingSphere) -> Vector3 {
+    let d      = point - sphere.center;
+    let length = d.length();
+    if length <= sphere.radius {
+        return point;
+    }
+    sphere.center + (d * (sphere.radius / length))
+}
+
+pub fn closest_point_on_plane(point: Vector3, plane: Plane) -> Vector3 {
+    let normal = Vector3::new(plane.a, plane.b, plane.c);
+    let t = Plane::dot_coordinate(plane, point) / Vector3::dot(normal, normal);
+    point - (normal * t)
+}
+
+pub fn closest_points_segment_segment(p1: Vector3, q1: Vector3, p2: Vector3, q2: Vector3) -> (Vector3, Vector3) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r  = p1 - p2;
+    let a  = Vector3::dot(d1, d1);
+    let e  = Vector3::dot(d2, d2);
+    let f  = Vector3::dot(d2, r);
+    if a <= EPSILON && e <= EPSILON {
+        return (p1, p2);
+    }
+    let mut s;
+    let mut t;
+    if a <= EPSILON {
+        s = 0f32;
+        t = saturate(f / e);
+    } else {
+        let c = Vector3::dot(d1, r);
+        if e <= EPSILON {
+            t = 0f32;
+            s = saturate(-c / a);
+        } else {
+            let b     = Vector3::dot(d1, d2);
+            let denom = (a * e) - (b * b);
+            s = if denom != 0f32 { saturate(((b * f) - (c * e)) / denom) } else { 0f32 };
+            t = ((b * s) + f) / e;
+            if t < 0f32 {
+                t = 0f32;
+                s = saturate(-c / a);
+            } else if t > 1f32 {
+                t = 1f32;
+                s = saturate((b - c) / a);
+            }
+        }
+    }
+    (p1 + (d1 * s), p2 + (d2 * t))
+}
+
+// distance along the ray, in units of direction, to the side of the infinite
+// cylinder about start and end, counting only hits between the two ends. the
+// shared part of the capsule and cylinder ray tests.
+pub fn ray_cylinder_side(position: Vector3, direction: Vector3, start: Vector3, end: Vector3, radius: f32) -> Option<f32> {
+    let axis = end - start;
+    let oa   = position - start;
+    let baba = Vector3::dot(axis, axis);
+    let bard = Vector3::dot(axis, direction);
+    let baoa = Vector3::dot(axis, oa);
+    let a    = (baba * Vector3::dot(direction, direction)) - (bard * bard);
+    let b    = (baba * Vector3::dot(direction, oa)) - (baoa * bard);
+    let c    = ((baba * Vector3::dot(oa, oa)) - (baoa * baoa)) - ((radius * radius) * baba);
+    let h    = (b * b) - (a * c);
+    if a <= EPSILON || h < 0f32 {
+        return None;
+    }
+    let t = (-b - h.sqrt()) / a;
+    let y = baoa + (t * bard);
+    if t >= 0f32 && y >= 0f32 && y <= baba { Some(t) } else { None }
+}
+
+pub fn distance_squared_point_segment(point: Vector3, a: Vector3, b: Vector3) -> f32 {
+    (point - closest_point_on_segment(point, a, b)).length_squared()
+}
+
+pub fn distance_point_segment(point: Vector3, a: Vector3, b: Vector3) -> f32 {
+    distance_squared_point_segment(point, a, b).sqrt()
+}
+
+pub fn distance_squared_point_triangle(point: Vector3, a: Vector3, b: Vector3, c: Vector3) -> f32 {
+    (point - closest_point_on_triangle(point, a, b, c)).length_squared()
+}
+
+pub fn distance_point_triangle(point: Vector3, a: Vector3, b: Vector3, c: Vector3) -> f32 {
+    distance_squared_point_triangle(point, a, b, c).sqrt()
+}
+
+pub fn distance_squared_point_box(point: Vector3, bounds: BoundingBox) -> f32 {
+    (point - closest_point_on_box(point, bounds)).length_squared()
+}
+
+pub fn distance_point_box(point: Vector3, bounds: BoundingBox) -> f32 {
+    distance_squared_point_box(point, bounds).sqrt()
+}
+
+pub fn distance_squared_point_oriented_box(point: Vector3, center: Vector3, axes: [Vector3; 3], half_extents: Vector3) -> f32 {
+    (point - closest_point_on_oriented_box(point, center, axes, half_extents)).length_squared()
+}
+
+pub fn distance_point_oriented_box(point: Vector3, center: Vector3, axes: [Vector3; 3], half_extents: Vector3) -> f32 {
+    distance_squared_point_oriented_box(point, center, axes, half_extents).sqrt()
+}
+
+pub fn distance_squared_point_sphere(point: Vector3, sphere: BoundingSphere) -> f32 {
+    (point - closest_point_on_sphere(point, sphere)).length_squared()
+}
+
+pub fn distance_point_sphere(point: Vector3, sphere: BoundingSphere) -> f32 {
+    distance_squared_point_sphere(point, sphere).sqrt()
+}
+
+pub fn distance_squared_point_plane(point: Vector3, plane: Plane) -> f32 {
+    (point - closest_point_on_plane(point, plane)).length_squared()
+}
+
+pub fn distance_point_plane(point: Vector3, plane: Plane) -> f32 {
+    distance_squared_point_plane(point, plane).sqrt()
+}
+
+pub fn distance_squared_segment_segment(p1: Vector3, q1: Vector3, p2: Vector3, q2: Vector3) -> f32 {
+    let (c1, c2) = closest_points_segment_segment(p1, q1, p2, q2);
+    (c1 - c2).length_squared()
+}
+
+pub fn distance_segment_segment(p1: Vector3, q1: Vector3, p2: Vector3, q2: Vector3) -> f32 {
+    distance_squared_segment_segment(p1, q1, p2, q2).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Plane, BoundingBox, BoundingSphere};
+    use super::{closest_point_on_triangle, closest_point_on_segment, closest_point_on_box, closest_point_on_oriented_box,
+                closest_point_on_sphere, closest_point_on_plane, closest_points_segment_segment,
+                distance_point_triangle, distance_point_box, distance_segment_segment, ray_cylinder_side};
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-5f32
+    }
+
+    #[test]
+    fn closest_point_on_triangle_in_each_region() {
+        let (a, b, c) = (Vector3::zero(), Vector3::new(2f32, 0f32, 0f32), Vector3::new(0f32, 2f32, 0f32));
+        let cases = [
+            (Vector3::new(-1f32, -1f32, 1f32),  a),
+            (Vector3::new(3f32, -1f32, 0f32),   b),
+            (Vector3::new(-1f32, 3f32, 0f32),   c),
+            (Vector3::new(1f32, -1f32, 5f32),   Vector3::new(1f32, 0f32, 0f32)),
+            (Vector3::new(-1f32, 1f32, 0f32),   Vector3::new(0f32, 1f32, 0f32)),
+            (Vector3::new(2f32, 2f32, 3f32),    Vector3::new(1f32, 1f32, 0f32)),
+            (Vector3::new(0.5f32, 0.5f32, -4f32), Vector3::new(0.5f32, 0.5f32, 0f32))];
+        for &(point, expected) in &cases {
+            assert!(near(closest_point_on_triangle(point, a, b, c), expected));
+        }
+        assert!((distance_point_triangle(Vector3::new(0.5f32, 0.5f32, -4f32), a, b, c) - 4f32).abs() < 1e-5f32);
+    }
+
+    #[test]
+    fn closest_point_on_degenerate_triangles() {
+        let (a, c) = (Vector3::zero(), Vector3::new(2f32, 0f32, 0f32));
+        let point  = Vector3::new(1f32, 1f32, 0f32);
+        assert!(near(closest_point_on_triangle(point, a, a, c), Vector3::new(1f32, 0f32, 0f32)));
+        assert!(near(closest_point_on_triangle(point, a, Vector3::new(3f32, 0f32, 0f32), c), Vector3::new(1f32, 0f32, 0f32)));
+        assert!(near(closest_point_on_triangle(Vector3::new(5f32, 1f32, 0f32), c, a, Vector3::new(1f32, 0f32, 0f32)), c));
+        assert!(near(closest_point_on_triangle(point, c, c, c), c));
+    }
+
+    #[test]
+    fn closest_point_on_segment_box_sphere_and_plane() {
+        let (a, b) = (Vector3::zero(), Vector3::new(0f32, 4f32, 0f32));
+        assert!(near(closest_point_on_segment(Vector3::new(1f32, 2f32, 0f32), a, b), Vector3::new(0f32, 2f32, 0f32)));
+        assert!(near(closest_point_on_segment(Vector3::new(1f32, -2f32, 0f32), a, b), a));
+        assert!(near(closest_point_on_segment(Vector3::new(1f32, 2f32, 0f32), a, a), a));
+        let bounds = BoundingBox::new(Vector3::zero(), Vector3::one());
+        assert!(near(closest_point_on_box(Vector3::new(2f32, 0.5f32, -1f32), bounds), Vector3::new(1f32, 0.5f32, 0f32)));
+        assert!((distance_point_box(Vector3::new(2f32, 0.5f32, 0.5f32), bounds) - 1f32).abs() < 1e-5f32);
+        let sphere = BoundingSphere::new(Vector3::UNIT_X, 1f32);
+        assert!(near(closest_point_on_sphere(Vector3::new(4f32, 0f32, 0f32), sphere), Vector3::new(2f32, 0f32, 0f32)));
+        assert!(near(closest_point_on_sphere(Vector3::new(1.5f32, 0f32, 0f32), sphere), Vector3::new(1.5f32, 0f32, 0f32)));
+        let plane = Plane::new(0f32, 2f32, 0f32, -2f32);
+        assert!(near(closest_point_on_plane(Vector3::new(3f32, 5f32, 1f32), plane), Vector3::new(3f32, 1f32, 1f32)));
+    }
+
+    #[test]
+    fn closest_point_on_oriented_box_uses_its_axes() {
+        let half = 0.5f32.sqrt();
+        let axes = [Vector3::new(half, half, 0f32), Vector3::new(-half, half, 0f32), Vector3::UNIT_Z];
+        let extents = Vector3::new(1f32, 1f32, 1f32);
+        assert!(near(closest_point_on_oriented_box(Vector3::new(3f32, 0f32, 0f32), Vector3::zero(), axes, extents), Vector3::new(2f32 * half, 0f32, 0f32)));
+        assert!(near(closest_point_on_oriented_box(Vector3::new(0.2f32, 0.1f32, 0f32), Vector3::zero(), axes, extents), Vector3::new(0.2f32, 0.1f32, 0f32)));
+    }
+
+    #[test]
+    fn closest_points_between_segments() {
+        let (p, q) = closest_points_segment_segment(Vector3::new(-1f32, 0f32, 0f32), Vector3::new(1f32, 0f32, 0f32),
+                                                    Vector3::new(0f32, -1f32, 2f32), Vector3::new(0f32, 1f32, 2f32));
+        assert!(near(p, Vector3::zero()) && near(q, Vector3::new(0f32, 0f32, 2f32)));
+        let (p, q) = closest_points_segment_segment(Vector3::zero(), Vector3::UNIT_X, Vector3::new(3f32, 1f32, 0f32), Vector3::new(5f32, 1f32, 0f32));
+        assert!(near(p, Vector3::UNIT_X) && near(q, Vector3::new(3f32, 1f32, 0f32)));
+        let (p, q) = closest_points_segment_segment(Vector3::zero(), Vector3::zero(), Vector3::new(1f32, 1f32, 0f32), Vector3::new(1f32, 1f32, 0f32));
+        assert!(near(p, Vector3::zero()) && near(q, Vector3::new(1f32, 1f32, 0f32)));
+        assert!((distance_segment_segment(Vector3::zero(), Vector3::UNIT_X, Vector3::new(0f32, 2f32, 0f32), Vector3::new(1f32, 2f32, 0f32)) - 2f32).abs() < 1e-5f32);
+    }
+
+    #[test]
+    fn ray_against_the_side_of_a_cylinder() {
+        let (start, end) = (Vector3::zero(), Vector3::new(0f32, 4f32, 0f32));
+        let t = ray_cylinder_side(Vector3::new(-5f32, 2f32, 0f32), Vector3::UNIT_X, start, end, 1f32).unwrap();
+        assert!((t - 4f32).abs() < 1e-5f32);
+        assert!(ray_cylinder_side(Vector3::new(-5f32, 5f32, 0f32), Vector3::UNIT_X, start, end, 1f32).is_none());
+        assert!(ray_cylinder_side(Vector3::new(0f32, 10f32, 0f32), -Vector3::UNIT_Y, start, end, 1f32).is_none());
+        assert!(ray_cylinder_side(Vector3::new(-5f32, 2f32, 0f32), -Vector3::UNIT_X, start, end, 1f32).is_none());
+    }
+}
@@ -0,0 +1,185 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+// scalar counterparts of the vector helpers, in the spirit of xna's
+// MathHelper. the vector types delegate to these per component.
+
+pub const PI:         f32 = ::std::f32::consts::PI;
+pub const TAU:        f32 = PI * 2f32;
+pub const PI_OVER_2:  f32 = PI / 2f32;
+pub const PI_OVER_4:  f32 = PI / 4f32;
+pub const EPSILON:    f32 = 1e-6f32;
+
+pub fn lerp(value1: f32, value2: f32, amount: f32) -> f32 {
+    value1 + ((value2 - value1) * amount)
+}
+
+// the amount for which lerp(value1, value2, amount) == value.
+pub fn inverse_lerp(value1: f32, value2: f32, value: f32) -> f32 {
+    if (value2 - value1).abs() <= EPSILON {
+        return 0f32;
+    }
+    (value - value1) / (value2 - value1)
+}
+
+pub fn remap(value: f32, from_min: f32, from_max: f32, to_min: f32, to_max: f32) -> f32 {
+    lerp(to_min, to_max, inverse_lerp(from_min, from_max, value))
+}
+
+pub fn clamp(value: f32, min: f32, max: f32) -> f32 {
+    let value = if value > max { max } else { value };
+    if value < min { min } else { value }
+}
+
+pub fn smooth_step(value1: f32, value2: f32, amount: f32) -> f32 {
+    let amount = clamp(amount, 0f32, 1f32);
+    lerp(value1, value2, (amount * amount) * (3f32 - (2f32 * amount)))
+}
+
+pub fn smoother_step(value1: f32, value2: f32, amount: f32) -> f32 {
+    let amount = clamp(amount, 0f32, 1f32);
+    lerp(value1, value2, ((amount * amount) * amount) * ((amount * ((amount * 6f32) - 15f32)) + 10f32))
+}
+
+pub fn hermite(value1: f32, tangent1: f32, value2: f32, tangent2: f32, amount: f32) -> f32 {
+    let num  = amount * amount;
+    let num2 = amount * num;
+    let num6 = ((2f32 * num2) - (3f32 * num)) + 1f32;
+    let num5 = (-2f32 * num2) + (3f32 * num);
+    let num4 = (num2 - (2f32 * num)) + amount;
+    let num3 = num2 - num;
+    (((value1 * num6) + (value2 * num5)) + (tangent1 * num4)) + (tangent2 * num3)
+}
+
+// derivative of hermite with respect to amount.
+pub fn hermite_derivative(value1: f32, tangent1: f32, value2: f32, tangent2: f32, amount: f32) -> f32 {
+    let num  = amount * amount;
+    let num6 = (6f32 * num) - (6f32 * amount);
+    let num5 = (-6f32 * num) + (6f32 * amount);
+    let num4 = ((3f32 * num) - (4f32 * amount)) + 1f32;
+    let num3 = (3f32 * num) - (2f32 * amount);
+    (((value1 * num6) + (value2 * num5)) + (tangent1 * num4)) + (tangent2 * num3)
+}
+
+pub fn catmull_rom(value1: f32, value2: f32, value3: f32, value4: f32, amount: f32) -> f32 {
+    let num  = amount * amount;
+    let num2 = amount * num;
+    0.5f32 * ((((2f32 * value2) + ((-value1 + value3) * amount)) + (((((2f32 * value1) - (5f32 * value2)) + (4f32 * value3)) - value4) * num)) + ((((-value1 + (3f32 * value2)) - (3f32 * value3)) + value4) * num2))
+}
+
+pub fn barycentric(value1: f32, value2: f32, value3: f32, amount1: f32, amount2: f32) -> f32 {
+    (value1 + (amount1 * (value2 - value1))) + (amount2 * (value3 - value1))
+}
+
+// wraps into [0, full). rem_euclid of a tiny negative value rounds up to
+// full itself, which is folded back to 0.
+pub fn wrap(value: f32, full: f32) -> f32 {
+    let wrapped = value.rem_euclid(full);
+    if wrapped < full { wrapped } else { 0f32 }
+}
+
+// wraps into [-pi, pi).
+pub fn wrap_angle(angle: f32) -> f32 {
+    wrap(angle + PI, TAU) - PI
+}
+
+pub fn approx_eq(value1: f32, value2: f32, tolerance: f32) -> bool {
+    (value1 - value2).abs() <= tolerance
+}
+
+pub fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    if (target - current).abs() <= max_delta {
+        return target;
+    }
+    current + ((target - current).signum() * max_delta)
+}
+
+// critically damped spring towards target, reaching it in roughly smooth_time.
+// velocity carries state between calls.
+pub fn smooth_damp(current: f32, target: f32, velocity: &mut f32, smooth_time: f32, delta_time: f32) -> f32 {
+    let smooth_time = smooth_time.max(EPSILON);
+    let omega  = 2f32 / smooth_time;
+    let x      = omega * delta_time;
+    let exp    = 1f32 / (((1f32 + x) + ((0.48f32 * x) * x)) + (((0.235f32 * x) * x) * x));
+    let change = current - target;
+    let temp   = (*velocity + (omega * change)) * delta_time;
+    *velocity  = (*velocity - (omega * temp)) * exp;
+    let mut result = target + ((change + temp) * exp);
+    if ((target - current) > 0f32) == (result > target) {
+        result    = target;
+        *velocity = 0f32;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PI, TAU, wrap, wrap_angle, inverse_lerp, remap, smooth_damp, move_towards};
+
+    #[test]
+    fn wrap_angle_stays_in_range() {
+        for &(angle, expected) in &[(0f32, 0f32), (PI, -PI), (-PI, -PI), (3f32 * PI, -PI), (PI * 0.5f32 + TAU, PI * 0.5f32), (-PI * 0.5f32 - TAU, -PI * 0.5f32)] {
+            assert!((wrap_angle(angle) - expected).abs() < 1e-5f32);
+        }
+        let below = f32::from_bits((-PI).to_bits() + 1);
+        assert!((-PI..PI).contains(&wrap_angle(below)));
+        // rem_euclid rounds a tiny negative value up to tau itself.
+        assert_eq!((-1e-8f32).rem_euclid(TAU), TAU);
+        assert_eq!(wrap(-1e-8f32, TAU), 0f32);
+        assert_eq!(wrap(-1e-6f32, 360f32), 0f32);
+    }
+
+    #[test]
+    fn inverse_lerp_and_remap() {
+        assert_eq!(inverse_lerp(2f32, 6f32, 3f32), 0.25f32);
+        assert_eq!(inverse_lerp(6f32, 2f32, 3f32), 0.75f32);
+        assert_eq!(inverse_lerp(2f32, 6f32, 8f32), 1.5f32);
+        assert_eq!(inverse_lerp(2f32, 2f32, 3f32), 0f32);
+        assert_eq!(remap(5f32, 0f32, 10f32, -1f32, 1f32), 0f32);
+    }
+
+    #[test]
+    fn smooth_damp_settles_without_overshoot() {
+        let (mut value, mut velocity) = (0f32, 0f32);
+        let mut previous = value;
+        for _ in 0..120 {
+            value = smooth_damp(value, 10f32, &mut velocity, 0.25f32, 1f32 / 60f32);
+            assert!(value >= previous && value <= 10f32);
+            previous = value;
+        }
+        assert!((value - 10f32).abs() < 0.01f32);
+        let mut velocity = 0f32;
+        let value = smooth_damp(0f32, 10f32, &mut velocity, 0.1f32, 100f32);
+        assert!(value <= 10f32 && (value - 10f32).abs() < 1e-4f32);
+        let mut velocity = 50f32;
+        assert_eq!(smooth_damp(9.9f32, 10f32, &mut velocity, 0.1f32, 0.1f32), 10f32);
+        assert_eq!(velocity, 0f32);
+        assert_eq!(move_towards(0f32, 10f32, 3f32), 3f32);
+        assert_eq!(move_towards(9f32, 10f32, 3f32), 10f32);
+    }
+}
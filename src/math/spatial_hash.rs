@@ -0,0 +1,368 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::integer_vector::{IVector2, IVector3};
+
+// uniform grid hashed by integer cell coordinates, for data that moves every
+// frame. positions are stored by index, so rebuilding from a particle buffer
+// is one pass and query results index back into that buffer.
+
+pub trait Position: Copy {
+    type Cell: Copy + Eq + Hash;
+    fn cell(self, inverse_cell_size: f32) -> Self::Cell;
+    // saturates at the ends of the i32 range.
+    fn offset(cell: Self::Cell, amount: i32) -> Self::Cell;
+    fn min_cell(cell1: Self::Cell, cell2: Self::Cell) -> Self::Cell;
+    fn max_cell(cell1: Self::Cell, cell2: Self::Cell) -> Self::Cell;
+    fn for_each_cell<F: FnMut(Self::Cell)>(min: Self::Cell, max: Self::Cell, f: F);
+    fn cell_count(min: Self::Cell, max: Self::Cell) -> usize;
+    fn contains_cell(min: Self::Cell, max: Self::Cell, cell: Self::Cell) -> bool;
+    fn distance_squared(value1: Self, value2: Self) -> f32;
+}
+
+impl Position for Vector2 {
+    type Cell = IVector2;
+    fn cell(self, inverse_cell_size: f32) -> IVector2 {
+        IVector2::floor(self * inverse_cell_size)
+    }
+    fn offset(cell: IVector2, amount: i32) -> IVector2 {
+        IVector2::new(cell.x.saturating_add(amount), cell.y.saturating_add(amount))
+    }
+    fn min_cell(cell1: IVector2, cell2: IVector2) -> IVector2 {
+        IVector2::min(cell1, cell2)
+    }
+    fn max_cell(cell1: IVector2, cell2: IVector2) -> IVector2 {
+        IVector2::max(cell1, cell2)
+    }
+    fn for_each_cell<F: FnMut(IVector2)>(min: IVector2, max: IVector2, mut f: F) {
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                f(IVector2::new(x, y));
+            }
+        }
+    }
+    fn cell_count(min: IVector2, max: IVector2) -> usize {
+        let size = |min: i32, max: i32| ((max as i64 - min as i64) + 1) as usize;
+        size(min.x, max.x).saturating_mul(size(min.y, max.y))
+    }
+    fn contains_cell(min: IVector2, max: IVector2, cell: IVector2) -> bool {
+        cell.x >= min.x && cell.x <= max.x && cell.y >= min.y && cell.y <= max.y
+    }
+    fn distance_squared(value1: Vector2, value2: Vector2) -> f32 {
+        (value1 - value2).length_squared()
+    }
+}
+
+impl Position for Vector3 {
+    type Cell = IVector3;
+    fn cell(self, inverse_cell_size: f32) -> IVector3 {
+        IVector3::floor(self * inverse_cell_size)
+    }
+    fn offset(cell: IVector3, amount: i32) -> IVector3 {
+        IVector3::new(cell.x.saturating_add(amount), cell.y.saturating_add(amount), cell.z.saturating_add(amount))
+    }
+    fn min_cell(cell1: IVector3, cell2: IVector3) -> IVector3 {
+        IVector3::min(cell1, cell2)
+    }
+    fn max_cell(cell1: IVector3, cell2: IVector3) -> IVector3 {
+        IVector3::max(cell1, cell2)
+    }
+    fn for_each_cell<F: FnMut(IVector3)>(min: IVector3, max: IVector3, mut f: F) {
+        for z in min.z..=max.z {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    f(IVector3::new(x, y, z));
+                }
+            }
+        }
+    }
+    fn cell_count(min: IVector3, max: IVector3) -> usize {
+        let size = |min: i32, max: i32| ((max as i64 - min as i64) + 1) as usize;
+        size(min.x, max.x).saturating_mul(size(min.y, max.y)).saturating_mul(size(min.z, max.z))
+    }
+    fn contains_cell(min: IVector3, max: IVector3, cell: IVector3) -> bool {
+        cell.x >= min.x && cell.x <= max.x &&
+        cell.y >= min.y && cell.y <= max.y &&
+        cell.z >= min.z && cell.z <= max.z
+    }
+    fn distance_squared(value1: Vector3, value2: Vector3) -> f32 {
+        (value1 - value2).length_squared()
+    }
+}
+
+pub struct SpatialHash<P: Position> {
+    cell_size:         f32,
+    inverse_cell_size: f32,
+    positions:         Vec<P>,
+    cells:             HashMap<P::Cell, Vec<usize>>
+}
+
+impl<P: Position> SpatialHash<P> {
+
+    pub fn new(cell_size: f32) -> SpatialHash<P> {
+        SpatialHash {
+            cell_size,
+            inverse_cell_size: 1f32 / cell_size,
+            positions:         Vec::new(),
+            cells:             HashMap::new()
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn positions(&self) -> &[P] {
+        &self.positions
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn cell(&self, position: P) -> P::Cell {
+        position.cell(self.inverse_cell_size)
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.cells.clear();
+    }
+
+    // replaces the contents. cell buckets that stay occupied keep their
+    // allocations across frames.
+    pub fn rebuild(&mut self, positions: &[P]) {
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.positions.clear();
+        self.positions.extend_from_slice(positions);
+        for (index, position) in positions.iter().enumerate() {
+            let cell = position.cell(self.inverse_cell_size);
+            self.cells.entry(cell).or_default().push(index);
+        }
+        self.cells.retain(|_, bucket| !bucket.is_empty());
+    }
+
+    pub fn insert(&mut self, position: P) -> usize {
+        let index = self.positions.len();
+        self.positions.push(position);
+        let cell = position.cell(self.inverse_cell_size);
+        self.cells.entry(cell).or_default().push(index);
+        index
+    }
+
+    // indices of positions within radius of center, in no particular order.
+    pub fn query_radius(&self, center: P, radius: f32) -> Vec<usize> {
+        let mut result = Vec::new();
+        let radius_squared = radius * radius;
+        self.for_each_in_cells(center, radius, |index| {
+            if P::distance_squared(center, self.positions[index]) <= radius_squared {
+                result.push(index);
+            }
+        });
+        result
+    }
+
+    // indices of the k positions nearest to center, nearest first. the search
+    // radius doubles from one cell until k positions are found inside it, or
+    // the search box covers every occupied cell.
+    pub fn nearest_k(&self, center: P, k: usize) -> Vec<usize> {
+        let k = k.min(self.positions.len());
+        if k == 0 {
+            return Vec::new();
+        }
+        let (low, high) = self.occupied_cells();
+        let mut radius = self.cell_size;
+        loop {
+            let (min, max) = self.cell_range(center, radius);
+            let covers = (P::contains_cell(min, max, low) && P::contains_cell(min, max, high)) || radius.is_infinite();
+            let mut visited: Vec<(f32, usize)> = Vec::new();
+            if covers {
+                visited.extend(self.positions.iter().enumerate().map(|(index, position)| (P::distance_squared(center, *position), index)));
+            } else {
+                self.for_each_in_cells(center, radius, |index| {
+                    visited.push((P::distance_squared(center, self.positions[index]), index));
+                });
+            }
+            let radius_squared = radius * radius;
+            let inside = visited.iter().filter(|v| v.0 <= radius_squared).count();
+            // everything inside the radius was visited, so the k nearest are
+            // known once k lie inside it or nothing is left unvisited.
+            if inside >= k || covers {
+                visited.sort_by(|a, b| a.0.total_cmp(&b.0));
+                return visited.into_iter().take(k).map(|(_, index)| index).collect();
+            }
+            radius *= 2f32;
+        }
+    }
+
+    pub fn nearest(&self, center: P) -> Option<usize> {
+        self.nearest_k(center, 1).first().cloned()
+    }
+
+    // pairs (i, j) with i < j whose positions share or neighbour a cell. with a
+    // cell size of at least the interaction distance this covers every pair
+    // closer than that distance.
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (cell, bucket) in &self.cells {
+            let min = P::offset(*cell, -1);
+            let max = P::offset(*cell, 1);
+            P::for_each_cell(min, max, |neighbour| {
+                if let Some(others) = self.cells.get(&neighbour) {
+                    for &i in bucket {
+                        for &j in others {
+                            if i < j {
+                                pairs.push((i, j));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+        pairs
+    }
+
+    // the smallest and largest occupied cell on each axis.
+    fn occupied_cells(&self) -> (P::Cell, P::Cell) {
+        let mut keys = self.cells.keys();
+        let first = *keys.next().expect("occupied cells of an empty hash");
+        keys.fold((first, first), |(low, high), key| (P::min_cell(low, *key), P::max_cell(high, *key)))
+    }
+
+    fn cell_range(&self, center: P, radius: f32) -> (P::Cell, P::Cell) {
+        let cell  = center.cell(self.inverse_cell_size);
+        let reach = (radius * self.inverse_cell_size).ceil() as i32;
+        (P::offset(cell, -reach), P::offset(cell, reach))
+    }
+
+    fn for_each_in_cells<F: FnMut(usize)>(&self, center: P, radius: f32, mut f: F) {
+        let (min, max) = self.cell_range(center, radius);
+        // large radii over sparse data walk the occupied cells instead.
+        if P::cell_count(min, max) > self.cells.len() {
+            for (key, bucket) in &self.cells {
+                if P::contains_cell(min, max, *key) {
+                    bucket.iter().for_each(|&index| f(index));
+                }
+            }
+        } else {
+            P::for_each_cell(min, max, |key| {
+                if let Some(bucket) = self.cells.get(&key) {
+                    bucket.iter().for_each(|&index| f(index));
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector2, Vector3};
+    use super::SpatialHash;
+
+    fn scatter(count: usize) -> Vec<Vector3> {
+        (0..count).map(|i| {
+            let t = i as f32;
+            Vector3::new((t * 7.31f32) % 50f32 - 25f32, (t * 3.17f32) % 40f32 - 20f32, (t * 1.93f32) % 30f32 - 15f32)
+        }).collect()
+    }
+
+    fn brute_nearest(positions: &[Vector3], center: Vector3, k: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..positions.len()).collect();
+        indices.sort_by(|&a, &b| (positions[a] - center).length_squared().total_cmp(&(positions[b] - center).length_squared()));
+        indices.truncate(k);
+        indices
+    }
+
+    #[test]
+    fn query_radius_matches_brute_force() {
+        let positions = scatter(400);
+        let mut hash = SpatialHash::new(2f32);
+        hash.rebuild(&positions);
+        let center = Vector3::new(3f32, -2f32, 1f32);
+        let mut found = hash.query_radius(center, 6.5f32);
+        found.sort();
+        let expected: Vec<usize> = (0..positions.len()).filter(|&i| (positions[i] - center).length() <= 6.5f32).collect();
+        assert_eq!(found, expected);
+        assert_eq!(hash.query_radius(center, 1000f32).len(), positions.len());
+    }
+
+    #[test]
+    fn nearest_k_matches_brute_force() {
+        let positions = scatter(400);
+        let mut hash = SpatialHash::new(1.5f32);
+        hash.rebuild(&positions);
+        for &center in &[Vector3::zero(), Vector3::new(20f32, 20f32, 20f32), Vector3::new(-300f32, 0f32, 0f32)] {
+            for &k in &[1, 5, 40, 400, 1000] {
+                let found = hash.nearest_k(center, k);
+                let expected = brute_nearest(&positions, center, k);
+                assert_eq!(found.len(), expected.len());
+                let distance = |i: usize| (positions[i] - center).length_squared();
+                for (a, b) in found.iter().zip(&expected) {
+                    assert_eq!(distance(*a), distance(*b));
+                }
+            }
+        }
+        assert!(SpatialHash::<Vector3>::new(1f32).nearest(Vector3::zero()).is_none());
+    }
+
+    #[test]
+    fn nearest_terminates_on_far_positions() {
+        let mut hash = SpatialHash::new(1f32);
+        hash.insert(Vector2::new(1e30f32, -1e30f32));
+        hash.insert(Vector2::new(-1e30f32, 1e30f32));
+        assert_eq!(hash.nearest_k(Vector2::new(0f32, 0f32), 2).len(), 2);
+        assert_eq!(hash.nearest(Vector2::new(1e30f32, -1e30f32)), Some(0));
+        assert_eq!(hash.query_radius(Vector2::new(0f32, 0f32), 1f32).len(), 0);
+    }
+
+    #[test]
+    fn candidate_pairs_cover_close_pairs() {
+        let positions = scatter(300);
+        let mut hash = SpatialHash::new(3f32);
+        hash.rebuild(&positions);
+        let pairs = hash.candidate_pairs();
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                if (positions[i] - positions[j]).length() < 3f32 {
+                    assert!(pairs.contains(&(i, j)));
+                }
+            }
+        }
+        assert!(pairs.iter().all(|&(i, j)| i < j));
+    }
+}
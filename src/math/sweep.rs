@@ -0,0 +1,272 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::matrix::Matrix;
+use super::plane::Plane;
+use super::bounding_box::BoundingBox;
+use super::bounding_sphere::BoundingSphere;
+use super::capsule::Capsule;
+use super::triangle::Triangle;
+use super::query;
+use super::gjk::{self, Support, Transformed};
+use super::scalar::EPSILON;
+
+// continuous collision for shapes moving linearly over one step. velocities
+// are displacements over the step, so time is in [0, 1]. shapes that already
+// overlap report time 0.
+//
+// the normal is the surface normal of the second shape at the contact,
+// pointing towards the first, and point lies on the second shape's surface.
+
+const MAX_ITERATIONS: usize = 32;
+
+#[derive(Copy, Clone)]
+pub struct Impact {
+    pub time:   f32,
+    pub point:  Vector3,
+    pub normal: Vector3
+}
+
+pub fn sphere_plane(sphere: BoundingSphere, velocity: Vector3, plane: Plane) -> Option<Impact> {
+    let plane    = Plane::normalize(plane);
+    let normal   = Vector3::new(plane.a, plane.b, plane.c);
+    let distance = Plane::dot_coordinate(plane, sphere.center);
+    // work from the side of the plane the sphere starts on.
+    let (normal, distance) = if distance < 0f32 { (-normal, -distance) } else { (normal, distance) };
+    if distance <= sphere.radius {
+        return Some(Impact { time: 0f32, point: sphere.center - (normal * distance), normal });
+    }
+    let speed = Vector3::dot(normal, velocity);
+    if speed >= 0f32 {
+        return None;
+    }
+    let time = (sphere.radius - distance) / speed;
+    if time > 1f32 {
+        return None;
+    }
+    let center = sphere.center + (velocity * time);
+    Some(Impact { time, point: center - (normal * sphere.radius), normal })
+}
+
+pub fn sphere_sphere(a: BoundingSphere, velocity_a: Vector3, b: BoundingSphere, velocity_b: Vector3) -> Option<Impact> {
+    let relative = velocity_a - velocity_b;
+    let time = BoundingSphere::new(b.center, a.radius + b.radius).intersects_ray(a.center, relative)?;
+    if time > 1f32 {
+        return None;
+    }
+    let center_a = a.center + (velocity_a * time);
+    let center_b = b.center + (velocity_b * time);
+    let normal   = unit_or(center_a - center_b, -relative);
+    Some(Impact { time, point: center_b + (normal * b.radius), normal })
+}
+
+pub fn sphere_triangle(sphere: BoundingSphere, velocity: Vector3, triangle: Triangle) -> Option<Impact> {
+    let closest = triangle.closest_point(sphere.center);
+    if (sphere.center - closest).length_squared() <= sphere.radius * sphere.radius {
+        let normal = unit_or(sphere.center - closest, triangle.normal());
+        return Some(Impact { time: 0f32, point: closest, normal });
+    }
+    let mut best: Option<Impact> = None;
+    // the face, as a plane hit whose contact lands inside the triangle.
+    let normal = triangle.normal().normalize();
+    let plane  = Plane::new(normal.x, normal.y, normal.z, -Vector3::dot(normal, triangle.a));
+    if let Some(impact) = sphere_plane(sphere, velocity, plane) {
+        if triangle.contains_point(impact.point) {
+            best = Some(impact);
+        }
+    }
+    // edges and vertices, as a ray from the center against capsules of the radius.
+    for (start, end) in [(triangle.a, triangle.b), (triangle.b, triangle.c), (triangle.c, triangle.a)] {
+        let time = match Capsule::new(start, end, sphere.radius).intersects_ray(sphere.center, velocity) {
+            Some(time) if time <= 1f32 && best.is_none_or(|best| time < best.time) => time,
+            _ => continue
+        };
+        let center = sphere.center + (velocity * time);
+        let point  = query::closest_point_on_segment(center, start, end);
+        best = Some(Impact { time, point, normal: unit_or(center - point, -velocity) });
+    }
+    best
+}
+
+pub fn box_box(a: BoundingBox, velocity_a: Vector3, b: BoundingBox, velocity_b: Vector3) -> Option<Impact> {
+    // b held still, a moving by the relative velocity.
+    let relative = velocity_a - velocity_b;
+    let mut first = 0f32;
+    let mut last  = 1f32;
+    let mut axis  = None;
+    for i in 0..3 {
+        if a.max[i] < b.min[i] {
+            if relative[i] <= 0f32 { return None; }
+            let time = (b.min[i] - a.max[i]) / relative[i];
+            if time > first { first = time; axis = Some((i, -1f32)); }
+            last = last.min((b.max[i] - a.min[i]) / relative[i]);
+        } else if a.min[i] > b.max[i] {
+            if relative[i] >= 0f32 { return None; }
+            let time = (b.max[i] - a.min[i]) / relative[i];
+            if time > first { first = time; axis = Some((i, 1f32)); }
+            last = last.min((b.min[i] - a.max[i]) / relative[i]);
+        } else if relative[i] > 0f32 {
+            last = last.min((b.max[i] - a.min[i]) / relative[i]);
+        } else if relative[i] < 0f32 {
+            last = last.min((b.min[i] - a.max[i]) / relative[i]);
+        }
+        if first > last {
+            return None;
+        }
+    }
+    let moved_a = BoundingBox::new(a.min + (velocity_a * first), a.max + (velocity_a * first));
+    let moved_b = BoundingBox::new(b.min + (velocity_b * first), b.max + (velocity_b * first));
+    let overlap = BoundingBox::new(Vector3::max(moved_a.min, moved_b.min), Vector3::min(moved_a.max, moved_b.max));
+    let mut normal = Vector3::zero();
+    match axis {
+        Some((i, sign)) => normal[i] = sign,
+        None            => normal = unit_or(moved_a.center() - moved_b.center(), Vector3::UNIT_Y)
+    }
+    Some(Impact { time: first, point: overlap.center(), normal })
+}
+
+// conservative advancement for any pair of convex shapes: steps forward by
+// the separating distance over the closing speed until the shapes are within
+// tolerance.
+pub fn conservative_advancement<A: Support, B: Support>(a: &A, velocity_a: Vector3, b: &B, velocity_b: Vector3, tolerance: f32) -> Option<Impact> {
+    let relative = velocity_a - velocity_b;
+    let mut time = 0f32;
+    for _ in 0..MAX_ITERATIONS {
+        let moved_a = Transformed::new(a, Matrix::translation(velocity_a * time));
+        let moved_b = Transformed::new(b, Matrix::translation(velocity_b * time));
+        let points = match gjk::closest_points(&moved_a, &moved_b) {
+            Some(points) => points,
+            None         => {
+                let contact = gjk::penetration(&moved_a, &moved_b)?;
+                return Some(Impact { time, point: contact.point_b, normal: -contact.normal });
+            }
+        };
+        let normal = unit_or(points.point_a - points.point_b, -relative);
+        if points.distance <= tolerance {
+            return Some(Impact { time, point: points.point_b, normal });
+        }
+        let closing = -Vector3::dot(relative, normal);
+        if closing <= EPSILON {
+            return None;
+        }
+        time += points.distance / closing;
+        if time > 1f32 {
+            return None;
+        }
+    }
+    None
+}
+
+fn unit_or(value: Vector3, fallback: Vector3) -> Vector3 {
+    let length = value.length();
+    if length > EPSILON { value / length } else { fallback.normalize() }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Plane, BoundingBox, BoundingSphere, Triangle};
+    use super::{sphere_plane, sphere_sphere, sphere_triangle, box_box, conservative_advancement};
+
+    #[test]
+    fn still_spheres_apart_never_meet() {
+        let a = BoundingSphere::new(Vector3::new(0f32, 0f32, 0f32), 1f32);
+        let b = BoundingSphere::new(Vector3::new(5f32, 0f32, 0f32), 1f32);
+        let velocity = Vector3::new(1f32, 2f32, 3f32);
+        assert!(sphere_sphere(a, velocity, b, velocity).is_none());
+        assert!(sphere_sphere(a, Vector3::zero(), b, Vector3::zero()).is_none());
+    }
+
+    #[test]
+    fn still_spheres_overlapping_hit_at_zero() {
+        let a = BoundingSphere::new(Vector3::new(0f32, 0f32, 0f32), 1f32);
+        let b = BoundingSphere::new(Vector3::new(1.5f32, 0f32, 0f32), 1f32);
+        let impact = sphere_sphere(a, Vector3::zero(), b, Vector3::zero()).unwrap();
+        assert_eq!(impact.time, 0f32);
+    }
+
+    #[test]
+    fn spheres_head_on() {
+        let a = BoundingSphere::new(Vector3::new(0f32, 0f32, 0f32), 1f32);
+        let b = BoundingSphere::new(Vector3::new(10f32, 0f32, 0f32), 1f32);
+        let impact = sphere_sphere(a, Vector3::new(8f32, 0f32, 0f32), b, Vector3::new(-8f32, 0f32, 0f32)).unwrap();
+        assert!((impact.time - 0.5f32).abs() < 1e-5f32);
+        assert!((impact.point - Vector3::new(5f32, 0f32, 0f32)).length() < 1e-4f32);
+        assert!((impact.normal - Vector3::new(-1f32, 0f32, 0f32)).length() < 1e-5f32);
+        assert!(sphere_sphere(a, Vector3::new(3f32, 0f32, 0f32), b, Vector3::zero()).is_none());
+    }
+
+    #[test]
+    fn sphere_falls_onto_plane() {
+        let sphere = BoundingSphere::new(Vector3::new(0f32, 5f32, 0f32), 1f32);
+        let ground = Plane::new(0f32, 1f32, 0f32, 0f32);
+        let impact = sphere_plane(sphere, Vector3::new(0f32, -8f32, 0f32), ground).unwrap();
+        assert!((impact.time - 0.5f32).abs() < 1e-5f32);
+        assert!(impact.point.length() < 1e-5f32);
+        assert!(sphere_plane(sphere, Vector3::new(0f32, 8f32, 0f32), ground).is_none());
+        assert!(sphere_plane(sphere, Vector3::new(0f32, -2f32, 0f32), ground).is_none());
+    }
+
+    #[test]
+    fn sphere_hits_triangle_face_and_edge() {
+        let triangle = Triangle::new(Vector3::new(-1f32, 0f32, -1f32), Vector3::new(1f32, 0f32, -1f32), Vector3::new(0f32, 0f32, 1f32));
+        let sphere   = BoundingSphere::new(Vector3::new(0f32, 3f32, 0f32), 1f32);
+        let impact = sphere_triangle(sphere, Vector3::new(0f32, -4f32, 0f32), triangle).unwrap();
+        assert!((impact.time - 0.5f32).abs() < 1e-5f32);
+        // passes beside the triangle, grazing nothing.
+        let beside = BoundingSphere::new(Vector3::new(4f32, 3f32, 0f32), 1f32);
+        assert!(sphere_triangle(beside, Vector3::new(0f32, -4f32, 0f32), triangle).is_none());
+        // slides sideways into the edge from z = -1.
+        let side = BoundingSphere::new(Vector3::new(0f32, 0f32, -4f32), 1f32);
+        let impact = sphere_triangle(side, Vector3::new(0f32, 0f32, 4f32), triangle).unwrap();
+        assert!((impact.time - 0.5f32).abs() < 1e-4f32);
+    }
+
+    #[test]
+    fn boxes_slide_into_each_other() {
+        let a = BoundingBox::new(Vector3::new(0f32, 0f32, 0f32), Vector3::new(1f32, 1f32, 1f32));
+        let b = BoundingBox::new(Vector3::new(3f32, 0.5f32, 0.5f32), Vector3::new(4f32, 1.5f32, 1.5f32));
+        let impact = box_box(a, Vector3::new(4f32, 0f32, 0f32), b, Vector3::zero()).unwrap();
+        assert!((impact.time - 0.5f32).abs() < 1e-5f32);
+        assert_eq!(impact.normal.to_array(), [-1f32, 0f32, 0f32]);
+        assert!(box_box(a, Vector3::new(1f32, 0f32, 0f32), b, Vector3::zero()).is_none());
+        assert!(box_box(a, Vector3::new(4f32, 4f32, 0f32), b, Vector3::zero()).is_none());
+        assert_eq!(box_box(a, Vector3::zero(), a, Vector3::zero()).unwrap().time, 0f32);
+    }
+
+    #[test]
+    fn conservative_advancement_matches_analytic() {
+        let a = BoundingSphere::new(Vector3::new(0f32, 0f32, 0f32), 1f32);
+        let b = BoundingSphere::new(Vector3::new(10f32, 1f32, 0f32), 1f32);
+        let velocity = Vector3::new(12f32, 0f32, 0f32);
+        let expected = sphere_sphere(a, velocity, b, Vector3::zero()).unwrap();
+        let impact   = conservative_advancement(&a, velocity, &b, Vector3::zero(), 1e-4f32).unwrap();
+        assert!((impact.time - expected.time).abs() < 1e-3f32);
+        assert!(conservative_advancement(&a, Vector3::new(0f32, 12f32, 0f32), &b, Vector3::zero(), 1e-4f32).is_none());
+    }
+}
@@ -0,0 +1,216 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector3::Vector3;
+use super::matrix::Matrix;
+use super::bounding_box::BoundingBox;
+use super::oriented_bounding_box::OrientedBoundingBox;
+use super::query;
+use super::scalar::EPSILON;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Triangle {
+    pub a: Vector3,
+    pub b: Vector3,
+    pub c: Vector3
+}
+
+impl Triangle {
+
+    pub fn new(a: Vector3, b: Vector3, c: Vector3) -> Triangle {
+        Triangle {
+            a,
+            b,
+            c
+        }
+    }
+
+    pub fn normal(&self) -> Vector3 {
+        Vector3::cross(self.b - self.a, self.c - self.a).normalize()
+    }
+
+    pub fn area(&self) -> f32 {
+        Vector3::cross(self.b - self.a, self.c - self.a).length() * 0.5f32
+    }
+
+    pub fn centroid(&self) -> Vector3 {
+        ((self.a + self.b) + self.c) * (1f32 / 3f32)
+    }
+
+    // returns the (u, v, w) weights of a, b and c. the inverse of
+    // Vector3::barycentric(a, b, c, v, w). all weight on a for degenerate
+    // triangles.
+    pub fn barycentric_coordinates(&self, point: Vector3) -> (f32, f32, f32) {
+        self.weights(point).unwrap_or((1f32, 0f32, 0f32))
+    }
+
+    fn weights(&self, point: Vector3) -> Option<(f32, f32, f32)> {
+        let v0    = self.b - self.a;
+        let v1    = self.c - self.a;
+        let v2    = point  - self.a;
+        let d00   = Vector3::dot(v0, v0);
+        let d01   = Vector3::dot(v0, v1);
+        let d11   = Vector3::dot(v1, v1);
+        let d20   = Vector3::dot(v2, v0);
+        let d21   = Vector3::dot(v2, v1);
+        // the squared sine of the angle at a, so the test holds at any scale.
+        let denom = (d00 * d11) - (d01 * d01);
+        if denom <= EPSILON * (d00 * d11) {
+            return None;
+        }
+        let v = ((d11 * d20) - (d01 * d21)) / denom;
+        let w = ((d00 * d21) - (d01 * d20)) / denom;
+        Some(((1f32 - v) - w, v, w))
+    }
+
+    // whether the point lies on the triangle, within a tolerance scaled by
+    // its coordinates. points off the plane are never contained.
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        let scale     = Vector3::max(Vector3::max(self.a.abs(), self.b.abs()), Vector3::max(self.c.abs(), point.abs())).max_element();
+        let tolerance = EPSILON * (1f32 + scale);
+        (self.closest_point(point) - point).length_squared() <= tolerance * tolerance
+    }
+
+    // whether the point, projected along the normal onto the plane of the
+    // triangle, lands inside it. false for degenerate triangles.
+    pub fn projection_contains_point(&self, point: Vector3) -> bool {
+        match self.weights(point) {
+            Some((u, v, w)) => u >= -EPSILON && v >= -EPSILON && w >= -EPSILON,
+            None            => false
+        }
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        query::closest_point_on_triangle(point, self.a, self.b, self.c)
+    }
+
+    pub fn intersects_ray(&self, position: Vector3, direction: Vector3) -> Option<f32> {
+        let edge1 = self.b - self.a;
+        let edge2 = self.c - self.a;
+        let p     = Vector3::cross(direction, edge2);
+        let det   = Vector3::dot(edge1, p);
+        // relative to the edge and direction lengths, so small triangles still hit.
+        if det.abs() <= EPSILON * ((edge1.length() * edge2.length()) * direction.length()) {
+            return None;
+        }
+        let inverse = 1f32 / det;
+        let s = position - self.a;
+        let u = Vector3::dot(s, p) * inverse;
+        if !(0f32..=1f32).contains(&u) {
+            return None;
+        }
+        let q = Vector3::cross(s, edge1);
+        let v = Vector3::dot(direction, q) * inverse;
+        if v < 0f32 || (u + v) > 1f32 {
+            return None;
+        }
+        let t = Vector3::dot(edge2, q) * inverse;
+        if t < 0f32 { None } else { Some(t) }
+    }
+
+    pub fn intersects_box(&self, bounds: BoundingBox) -> bool {
+        OrientedBoundingBox::from_box(bounds, Matrix::identity()).intersects_triangle(self.a, self.b, self.c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, BoundingBox};
+    use super::Triangle;
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-5f32
+    }
+
+    fn right() -> Triangle {
+        Triangle::new(Vector3::zero(), Vector3::new(2f32, 0f32, 0f32), Vector3::new(0f32, 2f32, 0f32))
+    }
+
+    #[test]
+    fn normal_area_and_centroid() {
+        let triangle = right();
+        assert!(near(triangle.normal(), Vector3::UNIT_Z));
+        assert!((triangle.area() - 2f32).abs() < 1e-6f32);
+        assert!(near(triangle.centroid(), Vector3::new(2f32 / 3f32, 2f32 / 3f32, 0f32)));
+    }
+
+    #[test]
+    fn barycentric_coordinates_invert_barycentric() {
+        let triangle = Triangle::new(Vector3::new(1f32, 2f32, 3f32), Vector3::new(-2f32, 0f32, 1f32), Vector3::new(0f32, 4f32, -1f32));
+        for &(v, w) in &[(0.2f32, 0.3f32), (0f32, 0f32), (1f32, 0f32), (0.5f32, 0.5f32), (-0.5f32, 1.25f32)] {
+            let point = Vector3::barycentric(triangle.a, triangle.b, triangle.c, v, w);
+            let (u, v2, w2) = triangle.barycentric_coordinates(point);
+            assert!((u - ((1f32 - v) - w)).abs() < 1e-5f32 && (v2 - v).abs() < 1e-5f32 && (w2 - w).abs() < 1e-5f32);
+        }
+        let degenerate = Triangle::new(Vector3::zero(), Vector3::UNIT_X, Vector3::new(2f32, 0f32, 0f32));
+        assert_eq!(degenerate.barycentric_coordinates(Vector3::UNIT_Y), (1f32, 0f32, 0f32));
+    }
+
+    #[test]
+    fn contains_point_on_and_off_the_plane() {
+        let triangle = right();
+        assert!(triangle.contains_point(Vector3::new(0.5f32, 0.5f32, 0f32)));
+        assert!(triangle.contains_point(Vector3::new(1f32, 1f32, 0f32)));
+        assert!(triangle.contains_point(triangle.c));
+        assert!(!triangle.contains_point(Vector3::new(1.5f32, 1.5f32, 0f32)));
+        assert!(!triangle.contains_point(Vector3::new(0.5f32, 0.5f32, 0.01f32)));
+        assert!(triangle.projection_contains_point(Vector3::new(0.5f32, 0.5f32, 0.01f32)));
+        assert!(!triangle.projection_contains_point(Vector3::new(1.5f32, 1.5f32, -3f32)));
+        let small = Triangle::new(Vector3::zero(), Vector3::new(1e-3f32, 0f32, 0f32), Vector3::new(0f32, 1e-3f32, 0f32));
+        assert!(small.projection_contains_point(Vector3::new(2e-4f32, 2e-4f32, 0f32)));
+        assert!(!small.projection_contains_point(Vector3::new(8e-4f32, 8e-4f32, 0f32)));
+        let degenerate = Triangle::new(Vector3::zero(), Vector3::UNIT_X, Vector3::new(2f32, 0f32, 0f32));
+        assert!(degenerate.contains_point(Vector3::new(1.5f32, 0f32, 0f32)));
+        assert!(!degenerate.contains_point(Vector3::UNIT_Y));
+        assert!(!degenerate.projection_contains_point(Vector3::UNIT_Y));
+    }
+
+    #[test]
+    fn ray_hit_and_miss() {
+        let triangle = right();
+        let t = triangle.intersects_ray(Vector3::new(0.5f32, 0.5f32, 3f32), -Vector3::UNIT_Z).unwrap();
+        assert!((t - 3f32).abs() < 1e-5f32);
+        assert!(triangle.intersects_ray(Vector3::new(0.5f32, 0.5f32, -3f32), Vector3::UNIT_Z * 2f32).is_some());
+        assert!(triangle.intersects_ray(Vector3::new(0.5f32, 0.5f32, 3f32), Vector3::UNIT_Z).is_none());
+        assert!(triangle.intersects_ray(Vector3::new(1.5f32, 1.5f32, 3f32), -Vector3::UNIT_Z).is_none());
+        assert!(triangle.intersects_ray(Vector3::new(-1f32, 0.5f32, 0f32), Vector3::UNIT_X).is_none());
+        let small = Triangle::new(Vector3::zero(), Vector3::new(1e-4f32, 0f32, 0f32), Vector3::new(0f32, 1e-4f32, 0f32));
+        let t = small.intersects_ray(Vector3::new(2e-5f32, 2e-5f32, 1f32), -Vector3::UNIT_Z).unwrap();
+        assert!((t - 1f32).abs() < 1e-5f32);
+    }
+
+    #[test]
+    fn closest_point_and_box() {
+        let triangle = right();
+        assert!(near(triangle.closest_point(Vector3::new(2f32, 2f32, 1f32)), Vector3::new(1f32, 1f32, 0f32)));
+        assert!(triangle.intersects_box(BoundingBox::new(Vector3::new(0.5f32, 0.5f32, -1f32), Vector3::new(0.6f32, 0.6f32, 1f32))));
+        assert!(!triangle.intersects_box(BoundingBox::new(Vector3::new(1.5f32, 1.5f32, -1f32), Vector3::new(2f32, 2f32, 1f32))));
+        assert!(!triangle.intersects_box(BoundingBox::new(Vector3::new(0.5f32, 0.5f32, 0.1f32), Vector3::new(0.6f32, 0.6f32, 1f32))));
+    }
+}
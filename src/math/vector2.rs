@@ -26,10 +26,13 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-use std::ops::{Add, Sub, Mul};
+use std::ops::{Add, Sub, Mul, Div, Neg};
 use std::fmt::{Display, Formatter, Error};
 use std::num;
+use super::bool_vector::BVector2;
+use super::scalar;
 
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Vector2 {
     pub x: f32,
@@ -83,6 +86,43 @@ impl Mul<f32> for Vector2 {
     }
 }
 
+impl Div<Vector2> for Vector2 {
+    type Output = Vector2;
+    fn div(self, rhs: Vector2) -> Vector2 {
+        Vector2 {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y
+        }
+    }
+}
+
+impl Div<f32> for Vector2 {
+    type Output = Vector2;
+    fn div(self, rhs: f32) -> Vector2 {
+        let num = 1f32 / rhs;
+        Vector2 {
+            x: self.x * num,
+            y: self.y * num
+        }
+    }
+}
+
+impl Neg for Vector2 {
+    type Output = Vector2;
+    fn neg(self) -> Vector2 {
+        Vector2 {
+            x: -self.x,
+            y: -self.y
+        }
+    }
+}
+
+impl_arithmetic_ops!(Vector2);
+impl_sum_product!(Vector2, Vector2::ZERO, Vector2::ONE);
+impl_component_index!(Vector2);
+impl_component_wise!(Vector2, x, y);
+impl_comparisons!(Vector2, BVector2, x, y);
+
 impl Display for Vector2 {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "({}, {})", self.x, self.y)
@@ -91,6 +131,11 @@ impl Display for Vector2 {
 
 impl Vector2 {
 
+    pub const ZERO:   Vector2 = Vector2::new(0f32, 0f32);
+    pub const ONE:    Vector2 = Vector2::new(1f32, 1f32);
+    pub const UNIT_X: Vector2 = Vector2::new(1f32, 0f32);
+    pub const UNIT_Y: Vector2 = Vector2::new(0f32, 1f32);
+
     pub fn length         (&self) -> f32 { ((self.x * self.x) + (self.y * self.y)).sqrt() }
     pub fn length_squared (&self) -> f32 { ((self.x * self.x) + (self.y * self.y)) }
     pub fn normalize      (&self) -> Vector2 {
@@ -101,49 +146,69 @@ impl Vector2 {
         }
     }
 
-    pub fn new (x:f32, y: f32) -> Vector2 {
+    pub const fn new (x:f32, y: f32) -> Vector2 {
         Vector2 { 
             x: x, 
             y: y 
         }
     }
 
-    pub fn zero() -> Vector2 {
+    pub const fn from_array(array: [f32; 2]) -> Vector2 {
+        Vector2::new(array[0], array[1])
+    }
+
+    pub const fn to_array(self) -> [f32; 2] {
+        [self.x, self.y]
+    }
+
+    pub fn as_array(&self) -> &[f32; 2] {
+        unsafe { &*(self as *const Vector2 as *const [f32; 2]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 2] {
+        unsafe { &mut *(self as *mut Vector2 as *mut [f32; 2]) }
+    }
+
+    pub const fn zero() -> Vector2 {
         Vector2 { 
             x: 0f32, 
             y: 0f32 
         }
     }
 
-    pub fn one() -> Vector2 {
+    pub const fn one() -> Vector2 {
         Vector2 { 
             x: 1f32, 
             y: 1f32 
         }
     }
 
-    pub fn up () -> Vector2 {
+    pub const fn up () -> Vector2 {
         Vector2 { 
             x: 0f32, 
             y: -1f32 
         }
     }
 
-    pub fn down() -> Vector2 {
+    pub const fn down() -> Vector2 {
         Vector2 { 
             x: 0f32, 
             y: 1f32 
         }
     }
 
-    pub fn left() -> Vector2 {
+    pub const fn left() -> Vector2 {
         Vector2 { 
             x: -1f32, 
             y: 0f32 
         }
     }
 
-    pub fn right() -> Vector2 {
+    pub const fn right() -> Vector2 {
         Vector2 { 
             x: 1f32, 
             y: 0f32 
@@ -186,31 +251,33 @@ impl Vector2 {
     }
 
     pub fn clamp(value1: Vector2, min: Vector2, max: Vector2) -> Vector2 {
-        let mut x = value1.x;
-        x = if x > max.x { max.x } else { x };
-        x = if x < min.x { min.x } else { x };
-        let mut y = value1.y;
-        y = if y > max.y { max.y } else { y };
-        y = if y < min.y { min.y } else { y };
-        Vector2 { x: x, y: y }
+        Vector2 {
+            x: scalar::clamp(value1.x, min.x, max.x),
+            y: scalar::clamp(value1.y, min.y, max.y)
+        }
     }
 
     pub fn lerp(value1: Vector2,
                 value2: Vector2,
                 amount: f32) -> Vector2 {
         Vector2 {
-            x: value1.x + ((value2.x - value1.x) * amount),
-            y: value1.y + ((value2.y - value1.y) * amount)
+            x: scalar::lerp(value1.x, value2.x, amount),
+            y: scalar::lerp(value1.y, value2.y, amount)
         }
     }
 
     pub fn barycentric(value1: Vector2, value2: Vector2, value3: Vector2,
                        amount1: f32, amount2: f32) -> Vector2 {
         Vector2 {
-            x: (value1.x + (amount1 * (value2.x - value1.x))) +
-               (amount2 * (value3.x - value1.x)),
-            y: (value1.y + (amount1 * (value2.y - value1.y))) +
-               (amount2 * (value3.y - value1.y))
+            x: scalar::barycentric(value1.x, value2.x, value3.x, amount1, amount2),
+            y: scalar::barycentric(value1.y, value2.y, value3.y, amount1, amount2)
+        }
+    }
+
+    pub fn hermite(value1: Vector2, tangent1: Vector2, value2: Vector2, tangent2: Vector2, amount: f32) -> Vector2 {
+        Vector2 {
+            x: scalar::hermite(value1.x, tangent1.x, value2.x, tangent2.x, amount),
+            y: scalar::hermite(value1.y, tangent1.y, value2.y, tangent2.y, amount)
         }
     }
 }
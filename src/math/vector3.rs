@@ -26,12 +26,17 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-use std::ops::{Add, Sub, Mul, Div};
+use std::ops::{Add, Sub, Mul, Div, Neg};
 use std::fmt::{Display, Formatter, Error};
 use std::num;
 use super::matrix::Matrix;
 use super::quaternion::Quaternion;
+use super::bool_vector::BVector3;
+use super::plane::Plane;
+use super::angle::Radians;
+use super::scalar;
 
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Vector3 {
     pub x: f32,
@@ -99,13 +104,38 @@ impl Div<f32> for Vector3 {
 	fn div(self, rhs: f32) -> Vector3 {
 		let num = 1f32 / rhs;
 		Vector3 {
-			x: self.x / num,
-			y: self.y / num,
-			z: self.z / num
+			x: self.x * num,
+			y: self.y * num,
+			z: self.z * num
 		}
 	}
 }
 
+impl Neg for Vector3 {
+    type Output = Vector3;
+    fn neg(self) -> Vector3 {
+        Vector3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z
+        }
+    }
+}
+
+impl Mul<Vector3> for Quaternion {
+    type Output = Vector3;
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        Vector3::transform_quaternion(rhs, self)
+    }
+}
+
+impl_arithmetic_ops!(Vector3);
+impl_ref_binop!(Quaternion, Vector3, Mul, mul);
+impl_sum_product!(Vector3, Vector3::ZERO, Vector3::ONE);
+impl_component_index!(Vector3);
+impl_component_wise!(Vector3, x, y, z);
+impl_comparisons!(Vector3, BVector3, x, y, z);
+
 impl Display for Vector3 {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "({}, {}, {})", self.x, self.y, self.z)
@@ -114,6 +144,12 @@ impl Display for Vector3 {
 
 impl Vector3 {
 
+    pub const ZERO:   Vector3 = Vector3::new(0f32, 0f32, 0f32);
+    pub const ONE:    Vector3 = Vector3::new(1f32, 1f32, 1f32);
+    pub const UNIT_X: Vector3 = Vector3::new(1f32, 0f32, 0f32);
+    pub const UNIT_Y: Vector3 = Vector3::new(0f32, 1f32, 0f32);
+    pub const UNIT_Z: Vector3 = Vector3::new(0f32, 0f32, 1f32);
+
     pub fn equals(&self, other:Vector3) -> bool {
         ((self.x == other.x) &&
          (self.y == other.y) &&
@@ -139,11 +175,31 @@ impl Vector3 {
                   z: self.z / length }
     }
 
-    pub fn new(x: f32, y: f32, z: f32) -> Vector3 {
+    pub const fn new(x: f32, y: f32, z: f32) -> Vector3 {
         Vector3 { x: x, y: y, z: z }
     }
 
-    pub fn one() -> Vector3 {
+    pub const fn from_array(array: [f32; 3]) -> Vector3 {
+        Vector3::new(array[0], array[1], array[2])
+    }
+
+    pub const fn to_array(self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn as_array(&self) -> &[f32; 3] {
+        unsafe { &*(self as *const Vector3 as *const [f32; 3]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 3] {
+        unsafe { &mut *(self as *mut Vector3 as *mut [f32; 3]) }
+    }
+
+    pub const fn one() -> Vector3 {
         Vector3 { 
             x: 1f32, 
             y: 1f32, 
@@ -151,7 +207,7 @@ impl Vector3 {
         }
     }
 
-    pub fn zero() -> Vector3 {
+    pub const fn zero() -> Vector3 {
         Vector3 { 
             x: 0f32, 
             y: 0f32, 
@@ -159,7 +215,7 @@ impl Vector3 {
         }
     }
 
-    pub fn up() -> Vector3 {
+    pub const fn up() -> Vector3 {
         Vector3 { 
             x: 0f32, 
             y: -1f32, 
@@ -167,7 +223,7 @@ impl Vector3 {
         }
     }
 
-    pub fn down() -> Vector3 {
+    pub const fn down() -> Vector3 {
         Vector3 { 
             x: 0f32, 
             y: 1f32, 
@@ -175,7 +231,7 @@ impl Vector3 {
         }
     }
 
-    pub fn left() -> Vector3 {
+    pub const fn left() -> Vector3 {
         Vector3 { 
             x: -1f32, 
             y: 0f32, 
@@ -183,7 +239,7 @@ impl Vector3 {
         }
     }
 
-    pub fn right() -> Vector3 {
+    pub const fn right() -> Vector3 {
         Vector3 { 
             x: 1f32, 
             y: 0f32,
@@ -191,7 +247,7 @@ impl Vector3 {
         }
     }
 
-    pub fn forward() -> Vector3 {
+    pub const fn forward() -> Vector3 {
         Vector3 { 
             x: 0f32, 
             y: 0f32, 
@@ -199,7 +255,7 @@ impl Vector3 {
         }
     }
 
-    pub fn backward() -> Vector3 {
+    pub const fn backward() -> Vector3 {
         Vector3 { 
             x: 0f32, 
             y: 0f32, 
@@ -268,16 +324,11 @@ impl Vector3 {
     pub fn clamp(value1: Vector3,
                  min: Vector3,
                  max: Vector3) -> Vector3 {
-        let mut x = value1.x;
-        x = if x > max.x { max.x } else { x };
-        x = if x < min.x { min.x } else { x };
-        let mut y = value1.y;
-        y = if y > max.y { max.y } else { y };
-        y = if y < min.y { min.y } else { y };
-        let mut z = value1.z;
-        z = if z > max.z { max.z } else { z };
-        z = if z < min.z { min.z } else { z };
-        Vector3 { x: x, y: y, z: z }
+        Vector3 {
+            x: scalar::clamp(value1.x, min.x, max.x),
+            y: scalar::clamp(value1.y, min.y, max.y),
+            z: scalar::clamp(value1.z, min.z, max.z)
+        }
     }
 
     pub fn barycentric(value1: Vector3,
@@ -286,23 +337,19 @@ impl Vector3 {
                        amount1: f32,
                        amount2: f32) -> Vector3 {
         Vector3 {
-            x: (value1.x + (amount1 * (value2.x - value1.x))) + (amount2 * (value3.x - value1.x)),
-            y: (value1.y + (amount1 * (value2.y - value1.y))) + (amount2 * (value3.y - value1.y)),
-            z: (value1.z + (amount1 * (value2.z - value1.z))) + (amount2 * (value3.z - value1.z))
+            x: scalar::barycentric(value1.x, value2.x, value3.x, amount1, amount2),
+            y: scalar::barycentric(value1.y, value2.y, value3.y, amount1, amount2),
+            z: scalar::barycentric(value1.z, value2.z, value3.z, amount1, amount2)
         }
     }
 
     pub fn smooth_step(value1: Vector3,
                        value2: Vector3,
                        amount: f32) -> Vector3 {
-        let mut _amount: f32 = if amount > 1f32 { 1f32 } else {
-            if amount < 0f32 { 0f32 } else { amount }
-        };
-        _amount = (_amount * _amount) * (3f32 - (2f32 * _amount));
         Vector3 {
-            x: value1.x + ((value2.x - value1.x) * _amount),
-            y: value1.y + ((value2.y - value1.y) * _amount),
-            z: value1.z + ((value2.z - value1.z) * _amount)
+            x: scalar::smooth_step(value1.x, value2.x, amount),
+            y: scalar::smooth_step(value1.y, value2.y, amount),
+            z: scalar::smooth_step(value1.z, value2.z, amount)
         }
     }
 
@@ -311,26 +358,18 @@ impl Vector3 {
                        value3: Vector3,
                        value4: Vector3,
                        amount: f32) -> Vector3 {
-        let num = amount * amount;
-        let num2 = amount * num;
         Vector3 {
-            x:  0.5f32 * ((((2f32 * value2.x) + ((-value1.x + value3.x) * amount)) + (((((2f32 * value1.x) - (5f32 * value2.x)) + (4f32 * value3.x)) - value4.x) * num)) + ((((-value1.x + (3f32 * value2.x)) - (3f32 * value3.x)) + value4.x) * num2)),
-            y:  0.5f32 * ((((2f32 * value2.y) + ((-value1.y + value3.y) * amount)) + (((((2f32 * value1.y) - (5f32 * value2.y)) + (4f32 * value3.y)) - value4.y) * num)) + ((((-value1.y + (3f32 * value2.y)) - (3f32 * value3.y)) + value4.y) * num2)),
-            z:  0.5f32 * ((((2f32 * value2.z) + ((-value1.z + value3.z) * amount)) + (((((2f32 * value1.z) - (5f32 * value2.z)) + (4f32 * value3.z)) - value4.z) * num)) + ((((-value1.z + (3f32 * value2.z)) - (3f32 * value3.z)) + value4.z) * num2))
+            x: scalar::catmull_rom(value1.x, value2.x, value3.x, value4.x, amount),
+            y: scalar::catmull_rom(value1.y, value2.y, value3.y, value4.y, amount),
+            z: scalar::catmull_rom(value1.z, value2.z, value3.z, value4.z, amount)
         }
     }
 
     pub fn hermite(value1: Vector3, tangent1: Vector3, value2: Vector3, tangent2: Vector3, amount: f32) -> Vector3 {
-        let num = amount * amount;
-        let num2 = amount * num;
-        let num6 = ((2f32 * num2) - (3f32 * num)) + 1f32;
-        let num5 = (-2f32 * num2) + (3f32 * num);
-        let num4 = (num2 - (2f32 * num)) + amount;
-        let num3 = num2 - num;
         Vector3 {
-            x: (((value1.x * num6) + (value2.x * num5)) + (tangent1.x * num4)) + (tangent2.x * num3),
-            y: (((value1.y * num6) + (value2.y * num5)) + (tangent1.y * num4)) + (tangent2.y * num3),
-            z: (((value1.z * num6) + (value2.z * num5)) + (tangent1.z * num4)) + (tangent2.z * num3)
+            x: scalar::hermite(value1.x, tangent1.x, value2.x, tangent2.x, amount),
+            y: scalar::hermite(value1.y, tangent1.y, value2.y, tangent2.y, amount),
+            z: scalar::hermite(value1.z, tangent1.z, value2.z, tangent2.z, amount)
         }
     }
 
@@ -369,4 +408,227 @@ impl Vector3 {
             z: ((value.x * (num6 - num10)) + (value.y * (num4 + num11))) + (value.z * ((1f32 - num8) - num5))
         }
     }
+
+    // unsigned angle in radians, in [0, pi].
+    pub fn angle_between(from: Vector3, to: Vector3) -> f32 {
+        let cross = Vector3::cross(from, to);
+        cross.length().atan2(Vector3::dot(from, to))
+    }
+
+    // angle in radians, in [-pi, pi], positive when counter clockwise about axis.
+    pub fn signed_angle(from: Vector3, to: Vector3, axis: Vector3) -> f32 {
+        let angle = Vector3::angle_between(from, to);
+        if Vector3::dot(axis, Vector3::cross(from, to)) < 0f32 { -angle } else { angle }
+    }
+
+    // on_normal need not be unit length.
+    pub fn project_on_vector(vector: Vector3, on_normal: Vector3) -> Vector3 {
+        let num = Vector3::dot(on_normal, on_normal);
+        if num <= scalar::EPSILON {
+            return Vector3::zero();
+        }
+        on_normal * (Vector3::dot(vector, on_normal) / num)
+    }
+
+    // removes the component along the plane normal. the plane distance is
+    // ignored, see query::closest_point_on_plane for positions.
+    pub fn project_on_plane(vector: Vector3, plane: Plane) -> Vector3 {
+        Vector3::reject(vector, Vector3::new(plane.a, plane.b, plane.c))
+    }
+
+    pub fn reject(vector: Vector3, on_normal: Vector3) -> Vector3 {
+        vector - Vector3::project_on_vector(vector, on_normal)
+    }
+
+    // vector and normal are expected to be unit length. returns zero on
+    // total internal reflection.
+    pub fn refract(vector: Vector3, normal: Vector3, eta: f32) -> Vector3 {
+        let num = Vector3::dot(normal, vector);
+        let k   = 1f32 - ((eta * eta) * (1f32 - (num * num)));
+        if k < 0f32 {
+            return Vector3::zero();
+        }
+        (vector * eta) - (normal * ((eta * num) + k.sqrt()))
+    }
+
+    pub fn lerp(value1: Vector3, value2: Vector3, amount: f32) -> Vector3 {
+        Vector3 {
+            x: scalar::lerp(value1.x, value2.x, amount),
+            y: scalar::lerp(value1.y, value2.y, amount),
+            z: scalar::lerp(value1.z, value2.z, amount)
+        }
+    }
+
+    // spherical interpolation of direction, linear interpolation of length.
+    pub fn slerp(from: Vector3, to: Vector3, amount: f32) -> Vector3 {
+        let length1 = from.length();
+        let length2 = to.length();
+        if length1 <= scalar::EPSILON || length2 <= scalar::EPSILON {
+            return Vector3::lerp(from, to, amount);
+        }
+        let direction1 = from / length1;
+        let direction2 = to / length2;
+        let length     = scalar::lerp(length1, length2, amount);
+        let num        = scalar::clamp(Vector3::dot(direction1, direction2), -1f32, 1f32);
+        if num > 1f32 - scalar::EPSILON {
+            return Vector3::nlerp(direction1, direction2, amount) * length;
+        }
+        let angle = num.acos();
+        // antiparallel: any great circle will do.
+        let direction2 = if num < -1f32 + scalar::EPSILON {
+            Vector3::any_orthogonal(direction1)
+        } else {
+            (direction2 - (direction1 * num)).normalize()
+        };
+        let theta = angle * amount;
+        ((direction1 * theta.cos()) + (direction2 * theta.sin())) * length
+    }
+
+    pub fn nlerp(from: Vector3, to: Vector3, amount: f32) -> Vector3 {
+        Vector3::lerp(from, to, amount).normalize()
+    }
+
+    // rotates current towards target by at most max_radians_delta and changes
+    // its length by at most max_magnitude_delta.
+    pub fn rotate_towards<A: Into<Radians>>(current: Vector3, target: Vector3, max_radians_delta: A, max_magnitude_delta: f32) -> Vector3 {
+        let max_radians_delta = max_radians_delta.into().0;
+        let length1 = current.length();
+        let length2 = target.length();
+        let length  = scalar::move_towards(length1, length2, max_magnitude_delta);
+        if length1 <= scalar::EPSILON || length2 <= scalar::EPSILON {
+            return Vector3::move_towards(current, target, max_magnitude_delta);
+        }
+        let angle = Vector3::angle_between(current, target);
+        if angle <= scalar::EPSILON {
+            return target * (length / length2);
+        }
+        let amount = (max_radians_delta / angle).min(1f32);
+        Vector3::slerp(current / length1, target / length2, amount) * length
+    }
+
+    // per component scalar::smooth_damp, with velocity carried between calls.
+    pub fn smooth_damp(current: Vector3, target: Vector3, velocity: &mut Vector3, smooth_time: f32, delta_time: f32) -> Vector3 {
+        Vector3 {
+            x: scalar::smooth_damp(current.x, target.x, &mut velocity.x, smooth_time, delta_time),
+            y: scalar::smooth_damp(current.y, target.y, &mut velocity.y, smooth_time, delta_time),
+            z: scalar::smooth_damp(current.z, target.z, &mut velocity.z, smooth_time, delta_time)
+        }
+    }
+
+    pub fn move_towards(current: Vector3, target: Vector3, max_distance: f32) -> Vector3 {
+        let delta    = target - current;
+        let distance = delta.length();
+        if distance <= max_distance || distance <= scalar::EPSILON {
+            return target;
+        }
+        current + (delta * (max_distance / distance))
+    }
+
+    // some unit vector perpendicular to value.
+    pub fn any_orthogonal(value: Vector3) -> Vector3 {
+        let orthogonal = if value.x.abs() > value.z.abs() {
+            Vector3::new(-value.y, value.x, 0f32)
+        } else {
+            Vector3::new(0f32, -value.z, value.y)
+        };
+        orthogonal.normalize()
+    }
+
+    // gram-schmidt. returns the unit normal and the unit tangent made
+    // perpendicular to it, picking any tangent when the two are parallel.
+    pub fn orthonormalize(normal: Vector3, tangent: Vector3) -> (Vector3, Vector3) {
+        let normal  = normal.normalize();
+        let tangent = tangent - (normal * Vector3::dot(tangent, normal));
+        if tangent.length_squared() <= scalar::EPSILON {
+            return (normal, Vector3::any_orthogonal(normal));
+        }
+        (normal, tangent.normalize())
+    }
+
+    // tangent and bitangent completing a right handed basis around a unit
+    // normal. frisvad's construction with the branchless fix of duff et al.
+    pub fn make_basis(normal: Vector3) -> (Vector3, Vector3) {
+        let sign = 1f32.copysign(normal.z);
+        let a    = -1f32 / (sign + normal.z);
+        let b    = (normal.x * normal.y) * a;
+        let tangent   = Vector3::new(1f32 + (((sign * normal.x) * normal.x) * a), sign * b, -sign * normal.x);
+        let bitangent = Vector3::new(b, sign + ((normal.y * normal.y) * a), -normal.y);
+        (tangent, bitangent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector3, Degrees};
+    use math::scalar::PI;
+
+    fn near(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-4f32
+    }
+
+    #[test]
+    fn divides_by_a_scalar() {
+        assert!((Vector3::new(2f32, 4f32, 6f32) / 2f32).equals(Vector3::new(1f32, 2f32, 3f32)));
+        let mut value = Vector3::new(-3f32, 9f32, 0.5f32);
+        value /= 0.5f32;
+        assert!(value.equals(Vector3::new(-6f32, 18f32, 1f32)));
+    }
+
+    #[test]
+    fn make_basis_is_orthonormal_and_right_handed() {
+        let normals = [Vector3::UNIT_X, Vector3::UNIT_Y, Vector3::UNIT_Z, -Vector3::UNIT_Z,
+                       Vector3::new(1e-4f32, 0f32, -1f32).normalize(), Vector3::new(0f32, -1e-7f32, -1f32).normalize(),
+                       Vector3::new(0.3f32, -0.5f32, -0.9999f32).normalize(), Vector3::new(1f32, 2f32, 3f32).normalize()];
+        for &normal in &normals {
+            let (tangent, bitangent) = Vector3::make_basis(normal);
+            assert!((tangent.length() - 1f32).abs() < 1e-5f32 && (bitangent.length() - 1f32).abs() < 1e-5f32);
+            assert!(Vector3::dot(tangent, normal).abs() < 1e-5f32);
+            assert!(Vector3::dot(bitangent, normal).abs() < 1e-5f32);
+            assert!(Vector3::dot(tangent, bitangent).abs() < 1e-5f32);
+            assert!(near(Vector3::cross(tangent, bitangent), normal));
+        }
+        let (normal, tangent) = Vector3::orthonormalize(Vector3::new(0f32, 0f32, 2f32), Vector3::new(1f32, 0f32, 1f32));
+        assert!(near(normal, Vector3::UNIT_Z) && near(tangent, Vector3::UNIT_X));
+        let (normal, tangent) = Vector3::orthonormalize(Vector3::UNIT_Y, Vector3::UNIT_Y * 3f32);
+        assert!(Vector3::dot(normal, tangent).abs() < 1e-6f32 && (tangent.length() - 1f32).abs() < 1e-6f32);
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let (from, to) = (Vector3::UNIT_X * 2f32, Vector3::UNIT_Y * 4f32);
+        assert!(near(Vector3::slerp(from, to, 0f32), from));
+        assert!(near(Vector3::slerp(from, to, 1f32), to));
+        let middle = Vector3::slerp(from, to, 0.5f32);
+        assert!((middle.length() - 3f32).abs() < 1e-5f32);
+        assert!((Vector3::angle_between(middle, from) - (PI * 0.25f32)).abs() < 1e-5f32);
+        // antiparallel directions turn through some perpendicular.
+        let middle = Vector3::slerp(Vector3::UNIT_Z, -Vector3::UNIT_Z, 0.5f32);
+        assert!((middle.length() - 1f32).abs() < 1e-5f32 && Vector3::dot(middle, Vector3::UNIT_Z).abs() < 1e-5f32);
+        assert!(near(Vector3::slerp(Vector3::UNIT_Z, -Vector3::UNIT_Z, 1f32), -Vector3::UNIT_Z));
+        assert!(near(Vector3::slerp(Vector3::zero(), Vector3::UNIT_X, 0.5f32), Vector3::UNIT_X * 0.5f32));
+    }
+
+    #[test]
+    fn rotate_towards_limits_angle_and_length() {
+        let step = Vector3::rotate_towards(Vector3::UNIT_X, Vector3::UNIT_Y * 3f32, Degrees(30f32), 0.5f32);
+        assert!((step.length() - 1.5f32).abs() < 1e-5f32);
+        assert!((Vector3::angle_between(step, Vector3::UNIT_X) - (PI / 6f32)).abs() < 1e-5f32);
+        assert!(near(Vector3::rotate_towards(Vector3::UNIT_X, Vector3::UNIT_Y, Degrees(120f32), 1f32), Vector3::UNIT_Y));
+        let turned = Vector3::rotate_towards(Vector3::UNIT_X, -Vector3::UNIT_X, Degrees(90f32), 0f32);
+        assert!((turned.length() - 1f32).abs() < 1e-5f32 && Vector3::dot(turned, Vector3::UNIT_X).abs() < 1e-5f32);
+    }
+
+    #[test]
+    fn refract_bends_and_reflects_internally() {
+        assert!(near(Vector3::refract(-Vector3::UNIT_Z, Vector3::UNIT_Z, 0.75f32), -Vector3::UNIT_Z));
+        let incoming = Vector3::new(1f32, 0f32, -1f32).normalize();
+        let refracted = Vector3::refract(incoming, Vector3::UNIT_Z, 1f32 / 1.5f32);
+        assert!((refracted.length() - 1f32).abs() < 1e-5f32);
+        assert!((refracted.x - (incoming.x / 1.5f32)).abs() < 1e-5f32 && refracted.z < 0f32);
+        // sin(60) * 1.5 is past 1, beyond the critical angle.
+        let steep = Vector3::new((PI / 3f32).sin(), 0f32, -(PI / 3f32).cos());
+        assert!(Vector3::refract(steep, Vector3::UNIT_Z, 1.5f32).equals(Vector3::zero()));
+        let shallow = Vector3::new((PI / 6f32).sin(), 0f32, -(PI / 6f32).cos());
+        assert!(!Vector3::refract(shallow, Vector3::UNIT_Z, 1.5f32).equals(Vector3::zero()));
+    }
 }
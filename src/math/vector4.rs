@@ -26,6 +26,11 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use super::matrix::Matrix;
+use super::bool_vector::BVector4;
+
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Vector4 {
     pub x: f32,
@@ -34,6 +39,155 @@ pub struct Vector4 {
     pub w: f32
 }
 
+impl Add<Vector4> for Vector4 {
+    type Output = Vector4;
+    fn add(self, rhs: Vector4) -> Vector4 {
+        Vector4 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w
+        }
+    }
+}
+
+impl Sub<Vector4> for Vector4 {
+    type Output = Vector4;
+    fn sub(self, rhs: Vector4) -> Vector4 {
+        Vector4 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w
+        }
+    }
+}
+
+impl Mul<Vector4> for Vector4 {
+    type Output = Vector4;
+    fn mul(self, rhs: Vector4) -> Vector4 {
+        Vector4 {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            z: self.z * rhs.z,
+            w: self.w * rhs.w
+        }
+    }
+}
+
+impl Mul<f32> for Vector4 {
+    type Output = Vector4;
+    fn mul(self, rhs: f32) -> Vector4 {
+        Vector4 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+            w: self.w * rhs
+        }
+    }
+}
+
+impl Div<Vector4> for Vector4 {
+    type Output = Vector4;
+    fn div(self, rhs: Vector4) -> Vector4 {
+        Vector4 {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y,
+            z: self.z / rhs.z,
+            w: self.w / rhs.w
+        }
+    }
+}
+
+impl Div<f32> for Vector4 {
+    type Output = Vector4;
+    fn div(self, rhs: f32) -> Vector4 {
+        let num = 1f32 / rhs;
+        Vector4 {
+            x: self.x * num,
+            y: self.y * num,
+            z: self.z * num,
+            w: self.w * num
+        }
+    }
+}
+
+impl Neg for Vector4 {
+    type Output = Vector4;
+    fn neg(self) -> Vector4 {
+        Vector4 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w
+        }
+    }
+}
+
+// row vector transform, the same convention as Vector3::transform.
+impl Mul<Matrix> for Vector4 {
+    type Output = Vector4;
+    fn mul(self, rhs: Matrix) -> Vector4 {
+        Vector4 {
+            x: (((self.x * rhs.m11) + (self.y * rhs.m21)) + (self.z * rhs.m31)) + (self.w * rhs.m41),
+            y: (((self.x * rhs.m12) + (self.y * rhs.m22)) + (self.z * rhs.m32)) + (self.w * rhs.m42),
+            z: (((self.x * rhs.m13) + (self.y * rhs.m23)) + (self.z * rhs.m33)) + (self.w * rhs.m43),
+            w: (((self.x * rhs.m14) + (self.y * rhs.m24)) + (self.z * rhs.m34)) + (self.w * rhs.m44)
+        }
+    }
+}
+
+impl_arithmetic_ops!(Vector4);
+impl_assign_op!(Vector4, Matrix, MulAssign, mul_assign, *);
+impl_ref_binop!(Vector4, Matrix, Mul, mul);
+impl_sum_product!(Vector4, Vector4::ZERO, Vector4::ONE);
+impl_component_index!(Vector4);
+impl_component_wise!(Vector4, x, y, z, w);
+impl_comparisons!(Vector4, BVector4, x, y, z, w);
+
 impl Vector4 {
 
+    pub const ZERO:   Vector4 = Vector4::new(0f32, 0f32, 0f32, 0f32);
+    pub const ONE:    Vector4 = Vector4::new(1f32, 1f32, 1f32, 1f32);
+    pub const UNIT_X: Vector4 = Vector4::new(1f32, 0f32, 0f32, 0f32);
+    pub const UNIT_Y: Vector4 = Vector4::new(0f32, 1f32, 0f32, 0f32);
+    pub const UNIT_Z: Vector4 = Vector4::new(0f32, 0f32, 1f32, 0f32);
+    pub const UNIT_W: Vector4 = Vector4::new(0f32, 0f32, 0f32, 1f32);
+
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Vector4 {
+        Vector4 {
+            x,
+            y,
+            z,
+            w
+        }
+    }
+
+    pub const fn from_array(array: [f32; 4]) -> Vector4 {
+        Vector4::new(array[0], array[1], array[2], array[3])
+    }
+
+    pub const fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    pub fn as_array(&self) -> &[f32; 4] {
+        unsafe { &*(self as *const Vector4 as *const [f32; 4]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 4] {
+        unsafe { &mut *(self as *mut Vector4 as *mut [f32; 4]) }
+    }
+
+    pub const fn zero() -> Vector4 {
+        Vector4::ZERO
+    }
+
+    pub const fn one() -> Vector4 {
+        Vector4::ONE
+    }
 }
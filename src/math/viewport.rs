@@ -0,0 +1,237 @@
+/*--------------------------------------------------------------------------
+
+acid::math
+
+The MIT License (MIT)
+
+Copyright (c) 2015 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::vector4::Vector4;
+use super::matrix::Matrix;
+use super::scalar::EPSILON;
+
+// screen rectangle and depth range, as in xna. pixel coordinates have their
+// origin at the top left with y down, ndc is [-1, 1] with y up, and uv is
+// [0, 1] over the viewport with y down.
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq)]
+pub struct Viewport {
+    pub x:         f32,
+    pub y:         f32,
+    pub width:     f32,
+    pub height:    f32,
+    pub min_depth: f32,
+    pub max_depth: f32
+}
+
+impl Viewport {
+
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Viewport {
+        Viewport {
+            x,
+            y,
+            width,
+            height,
+            min_depth: 0f32,
+            max_depth: 1f32
+        }
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        if self.height == 0f32 { 0f32 } else { self.width / self.height }
+    }
+
+    // world space point to pixel x, y and depth in [min_depth, max_depth].
+    // a point behind the camera comes out mirrored through the center of the
+    // viewport, see project_in_front.
+    pub fn project(&self, point: Vector3, projection: Matrix, view: Matrix, world: Matrix) -> Vector3 {
+        self.clip_to_pixel(clip(point, projection, view, world))
+    }
+
+    // as project, but none for points on or behind the camera plane.
+    pub fn project_in_front(&self, point: Vector3, projection: Matrix, view: Matrix, world: Matrix) -> Option<Vector3> {
+        let clip = clip(point, projection, view, world);
+        if clip.w <= EPSILON { None } else { Some(self.clip_to_pixel(clip)) }
+    }
+
+    fn clip_to_pixel(&self, clip: Vector4) -> Vector3 {
+        let ndc   = divide(clip);
+        let pixel = self.ndc_to_pixel(Vector2::new(ndc.x, ndc.y));
+        Vector3::new(pixel.x, pixel.y, (ndc.z * (self.max_depth - self.min_depth)) + self.min_depth)
+    }
+
+    // pixel x, y and depth back to world space. an empty depth range
+    // unprojects every depth onto the near plane.
+    pub fn unproject(&self, point: Vector3, projection: Matrix, view: Matrix, world: Matrix) -> Vector3 {
+        let ndc   = self.pixel_to_ndc(Vector2::new(point.x, point.y));
+        let range = self.max_depth - self.min_depth;
+        let depth = if range == 0f32 { 0f32 } else { (point.z - self.min_depth) / range };
+        let clip  = Vector4::new(ndc.x, ndc.y, depth, 1f32) * Matrix::invert((world * view) * projection);
+        divide(clip)
+    }
+
+    // world space ray through a pixel, from the near plane towards the far
+    // plane, with a unit direction. for picking.
+    pub fn unproject_ray(&self, pixel: Vector2, projection: Matrix, view: Matrix) -> (Vector3, Vector3) {
+        let near = self.unproject(Vector3::new(pixel.x, pixel.y, self.min_depth), projection, view, Matrix::identity());
+        let far  = self.unproject(Vector3::new(pixel.x, pixel.y, self.max_depth), projection, view, Matrix::identity());
+        (near, (far - near).normalize())
+    }
+
+    pub fn pixel_to_ndc(&self, pixel: Vector2) -> Vector2 {
+        Viewport::uv_to_ndc(self.pixel_to_uv(pixel))
+    }
+
+    pub fn ndc_to_pixel(&self, ndc: Vector2) -> Vector2 {
+        self.uv_to_pixel(Viewport::ndc_to_uv(ndc))
+    }
+
+    // 0 along an axis the viewport has no size in.
+    pub fn pixel_to_uv(&self, pixel: Vector2) -> Vector2 {
+        Vector2::new(if self.width  == 0f32 { 0f32 } else { (pixel.x - self.x) / self.width },
+                     if self.height == 0f32 { 0f32 } else { (pixel.y - self.y) / self.height })
+    }
+
+    pub fn uv_to_pixel(&self, uv: Vector2) -> Vector2 {
+        Vector2::new((uv.x * self.width) + self.x, (uv.y * self.height) + self.y)
+    }
+
+    pub fn uv_to_ndc(uv: Vector2) -> Vector2 {
+        Vector2::new((uv.x * 2f32) - 1f32, 1f32 - (uv.y * 2f32))
+    }
+
+    pub fn ndc_to_uv(ndc: Vector2) -> Vector2 {
+        Vector2::new((ndc.x + 1f32) * 0.5f32, (1f32 - ndc.y) * 0.5f32)
+    }
+}
+
+fn clip(point: Vector3, projection: Matrix, view: Matrix, world: Matrix) -> Vector4 {
+    Vector4::new(point.x, point.y, point.z, 1f32) * ((world * view) * projection)
+}
+
+// a point on the camera plane has w of 0 and no projection, it is returned
+// undivided rather than as infinities.
+fn divide(clip: Vector4) -> Vector3 {
+    let point = Vector3::new(clip.x, clip.y, clip.z);
+    if (clip.w - 1f32).abs() <= EPSILON || clip.w == 0f32 { point } else { point / clip.w }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::{Vector2, Vector3, Matrix, Degrees};
+    use super::Viewport;
+
+    fn viewport() -> Viewport {
+        Viewport::new(100f32, 50f32, 800f32, 600f32)
+    }
+
+    fn camera(viewport: &Viewport) -> (Matrix, Matrix) {
+        let projection = Matrix::perspective_fov(Degrees(60f32), viewport.aspect_ratio(), 0.1f32, 100f32);
+        let view = Matrix::look_at(Vector3::new(0f32, 0f32, 5f32), Vector3::zero(), Vector3::UNIT_Y);
+        (projection, view)
+    }
+
+    #[test]
+    fn aspect_ratio() {
+        assert!((viewport().aspect_ratio() - (4f32 / 3f32)).abs() < 1e-6f32);
+        assert_eq!(Viewport::new(0f32, 0f32, 800f32, 0f32).aspect_ratio(), 0f32);
+    }
+
+    #[test]
+    fn converts_between_pixel_ndc_and_uv() {
+        let viewport = viewport();
+        assert!(viewport.pixel_to_ndc(Vector2::new(100f32, 50f32)) == Vector2::new(-1f32, 1f32));
+        assert!(viewport.pixel_to_ndc(Vector2::new(900f32, 650f32)) == Vector2::new(1f32, -1f32));
+        assert!(viewport.pixel_to_ndc(Vector2::new(500f32, 350f32)) == Vector2::zero());
+        assert!(viewport.pixel_to_uv(Vector2::new(300f32, 200f32)) == Vector2::new(0.25f32, 0.25f32));
+        assert!(viewport.uv_to_pixel(Vector2::new(0.25f32, 0.25f32)) == Vector2::new(300f32, 200f32));
+        assert!(viewport.ndc_to_pixel(Vector2::new(0.5f32, -0.5f32)) == Vector2::new(700f32, 500f32));
+        let uv = Viewport::ndc_to_uv(Viewport::uv_to_ndc(Vector2::new(0.1f32, 0.7f32)));
+        assert!((uv - Vector2::new(0.1f32, 0.7f32)).length() < 1e-6f32);
+    }
+
+    #[test]
+    fn project_unproject_round_trip() {
+        let viewport = viewport();
+        let (projection, view) = camera(&viewport);
+        let world = Matrix::translation(Vector3::new(0.25f32, 0f32, -1f32));
+        let center = viewport.project(Vector3::new(-0.25f32, 0f32, 1f32), projection, view, world);
+        assert!((Vector2::new(center.x, center.y) - Vector2::new(500f32, 350f32)).length() < 1e-3f32);
+        for point in [Vector3::new(0.5f32, -0.3f32, 1f32), Vector3::new(-2f32, 1f32, -10f32), Vector3::new(0f32, 0.2f32, 4f32)] {
+            let pixel = viewport.project(point, projection, view, world);
+            assert!(pixel.z > 0f32 && pixel.z < 1f32);
+            let back = viewport.unproject(pixel, projection, view, world);
+            assert!((back - point).length() < 1e-3f32);
+        }
+        let above = viewport.project(Vector3::new(-0.25f32, 1f32, 1f32), projection, view, world);
+        assert!(above.y < center.y);
+    }
+
+    #[test]
+    fn unproject_ray_passes_through_the_projected_point() {
+        let viewport = viewport();
+        let (projection, view) = camera(&viewport);
+        let (position, direction) = viewport.unproject_ray(Vector2::new(500f32, 350f32), projection, view);
+        assert!((position - Vector3::new(0f32, 0f32, 4.9f32)).length() < 1e-3f32);
+        assert!((direction - Vector3::new(0f32, 0f32, -1f32)).length() < 1e-4f32);
+        let point = Vector3::new(1f32, -0.5f32, -2f32);
+        let pixel = viewport.project(point, projection, view, Matrix::identity());
+        let (position, direction) = viewport.unproject_ray(Vector2::new(pixel.x, pixel.y), projection, view);
+        assert!((direction.length() - 1f32).abs() < 1e-5f32);
+        assert!(Vector3::cross(point - position, direction).length() < 1e-3f32);
+    }
+
+    #[test]
+    fn points_behind_the_camera() {
+        let viewport = viewport();
+        let (projection, view) = camera(&viewport);
+        let world = Matrix::identity();
+        let front = Vector3::new(1f32, 0.5f32, 0f32);
+        let behind = Vector3::new(-1f32, -0.5f32, 10f32);
+        assert!(viewport.project_in_front(behind, projection, view, world).is_none());
+        assert!(viewport.project_in_front(Vector3::new(1f32, 0f32, 5f32), projection, view, world).is_none());
+        let pixel = viewport.project_in_front(front, projection, view, world).unwrap();
+        assert!(pixel.x > 500f32 && pixel.y < 350f32);
+        // project alone mirrors it back through the center.
+        let mirrored = viewport.project(behind, projection, view, world);
+        assert!(mirrored.x > 500f32 && mirrored.y < 350f32);
+        assert!(mirrored.z.is_finite());
+        let on_plane = viewport.project(Vector3::new(1f32, 0f32, 5f32), projection, view, world);
+        assert!(on_plane.x.is_finite() && on_plane.y.is_finite());
+    }
+
+    #[test]
+    fn empty_viewports_and_depth_ranges_stay_finite() {
+        let mut viewport = viewport();
+        let (projection, view) = camera(&viewport);
+        viewport.max_depth = viewport.min_depth;
+        let point = viewport.unproject(Vector3::new(500f32, 350f32, 0.5f32), projection, view, Matrix::identity());
+        assert!((point - Vector3::new(0f32, 0f32, 4.9f32)).length() < 1e-3f32);
+        let empty = Viewport::new(10f32, 20f32, 0f32, 0f32);
+        assert!(empty.pixel_to_uv(Vector2::new(15f32, 25f32)) == Vector2::zero());
+        assert!(empty.pixel_to_ndc(Vector2::new(15f32, 25f32)) == Vector2::new(-1f32, 1f32));
+    }
+}